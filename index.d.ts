@@ -1,11 +1,29 @@
 export {
     compile,
+    exportReflectionData,
     getArgumentNames,
+    getDependencies,
+    importReflectionData,
     isValidIdentifier,
     start,
     prepareStackTrace,
+    unregisterClass,
+    unregisterFile,
 } from './pkg/compiler';
 
+declare interface JsTypeReference {
+    name: string;
+    specifier?: string;
+    imported?: string;
+}
+
+declare interface JsTypeAnnotation {
+    text: string;
+    references: JsTypeReference[];
+}
+
+declare type Accessibility = 'public' | 'protected' | 'private';
+
 declare interface JsMethodParameter {
     name?: String;
     index: number;
@@ -13,6 +31,22 @@ declare interface JsMethodParameter {
     isObjectPattern: boolean;
     isArrayPattern: boolean;
     isRestElement: boolean;
+    type?: JsTypeAnnotation;
+    accessibility?: Accessibility;
+    isReadonly: boolean;
+}
+
+declare interface DocblockTag {
+    tag: string;
+    type?: string;
+    name?: string;
+    description?: string;
+}
+
+declare interface Docblock {
+    summary?: string;
+    description?: string;
+    tags: DocblockTag[];
 }
 
 declare interface JsMemberData {
@@ -21,9 +55,15 @@ declare interface JsMemberData {
     name: string | symbol;
     static?: boolean;
     private?: boolean;
+    computed?: boolean;
     access?: { get?: () => any; set?: (v: any) => void };
     parameters?: JsMethodParameter[];
+    type?: JsTypeAnnotation;
+    returnType?: JsTypeAnnotation;
+    accessibility?: Accessibility;
+    readonly?: boolean;
     docblock?: String;
+    parsedDocblock?: Docblock;
 }
 
 declare interface JsReflectionData {
@@ -32,9 +72,43 @@ declare interface JsReflectionData {
     namespace?: String;
     filename?: String;
     members: JsMemberData[];
+    implements: JsTypeReference[];
     docblock?: String;
+    parsedDocblock?: Docblock;
+}
+
+declare interface Diagnostic {
+    filename?: string;
+    span: { start: number; end: number };
+    line: number;
+    column: number;
+    kind: string;
+    message: string;
+    severity: 'error' | 'warning';
+    codeFrame?: string;
 }
 
+declare interface CompileResult {
+    code: string;
+    sourceMap?: string;
+    classIds: string[];
+    dependencies: string[];
+    warnings: Diagnostic[];
+}
+
+export function compileDetailed(
+    source: string,
+    filename?: string | null,
+    opts?: Parameters<typeof import('./pkg/compiler').compile>[2],
+): CompileResult;
+
 export function getReflectionData(
     classIdOrValue: any,
 ): JsReflectionData | undefined;
+
+export function getDesignMetadata(
+    target: Function,
+    key: 'design:type' | 'design:paramtypes' | 'design:returntype',
+    member?: string | symbol,
+    isStatic?: boolean,
+): any;
@@ -7,20 +7,34 @@ const isSimdSupported = WebAssembly.validate(
 );
 const {
     compile,
+    compileDetailed,
+    exportReflectionData,
     getArgumentNames,
+    getDependencies,
+    importReflectionData,
     isValidIdentifier,
     prepareStackTrace,
     start,
+    unregisterClass,
+    unregisterFile,
 } = isSimdSupported ? require('./simd/compiler') : require('./pkg/compiler');
 
 exports._isSimdSupported = isSimdSupported;
 exports.compile = compile;
+exports.compileDetailed = compileDetailed;
+exports.exportReflectionData = exportReflectionData;
 exports.getArgumentNames = getArgumentNames;
+exports.getDependencies = getDependencies;
+exports.importReflectionData = importReflectionData;
 exports.isValidIdentifier = isValidIdentifier;
 exports.prepareStackTrace = prepareStackTrace;
 exports.start = start;
+exports.unregisterClass = unregisterClass;
+exports.unregisterFile = unregisterFile;
 exports.getReflectionData = require('./lib/reflection').getReflectionData;
+exports.getDesignMetadata = require('./lib/metadata').getDesignMetadata;
 
 global._apply_decs_2203_r = require('./lib/_apply_decs_2203_r')._;
 global._construct_jobject = require('./lib/_construct_jobject')._;
 global.__jymfony_reflect = require('./lib/reflection')._;
+global.__jymfony_metadata = require('./lib/metadata')._;
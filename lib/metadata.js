@@ -0,0 +1,79 @@
+const membersKey = 'design:members';
+
+/**
+ * Returns the object stored under the given key, creating an own one
+ * (inheriting from the parent class one, if any) when needed.
+ */
+function ownObject(target, key) {
+    if (!Object.prototype.hasOwnProperty.call(target, key)) {
+        target[key] = Object.create(target[key] || null);
+    }
+
+    return target[key];
+}
+
+/**
+ * Stores a design-time type, as emitted by TypeScript `emitDecoratorMetadata`,
+ * into the decorator metadata. The type is resolved on first access.
+ * Class types are stored into the metadata itself, member types under
+ * `design:members`, by placement (`static` or `instance`) and name.
+ *
+ * @param {string} key
+ * @param {() => *} type
+ */
+exports._ = function __jymfony_metadata(key, type) {
+    return (value, context) => {
+        let target = context.metadata;
+        if (context.kind !== 'class') {
+            const members = ownObject(target, membersKey);
+            const placement = context.static ? 'static' : 'instance';
+            target = ownObject(ownObject(members, placement), context.name);
+        }
+
+        let resolved = false;
+        let resolvedType;
+        Object.defineProperty(target, key, {
+            configurable: true,
+            enumerable: true,
+            get: () => {
+                if (!resolved) {
+                    resolvedType = type();
+                    resolved = true;
+                }
+
+                return resolvedType;
+            },
+        });
+    };
+};
+
+/**
+ * Reads a design-time type of a class or of one of its members.
+ *
+ * @param {Function} target
+ * @param {string} key `design:type`, `design:paramtypes` or `design:returntype`
+ * @param {string|symbol|undefined} member The class itself if omitted
+ * @param {boolean} isStatic
+ */
+exports.getDesignMetadata = function getDesignMetadata(
+    target,
+    key,
+    member = undefined,
+    isStatic = false,
+) {
+    const sym = Symbol.metadata || Symbol.for('Symbol.metadata');
+    const metadata = target ? target[sym] : void 0;
+    if (metadata === void 0 || metadata === null) {
+        return undefined;
+    }
+
+    if (member === void 0) {
+        return metadata[key];
+    }
+
+    const members = metadata[membersKey];
+    const placement = members && members[isStatic ? 'static' : 'instance'];
+    const data = placement && placement[member];
+
+    return data ? data[key] : undefined;
+};
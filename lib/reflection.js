@@ -3,27 +3,95 @@ const { getInternalReflectionData } = require('..')._isSimdSupported
     : require('../pkg/compiler');
 
 const reflectionDataMap = new Map();
+const pendingInlineData = new Map();
 const reflectionSymbol = Symbol('jymfony.reflect');
+const inlineDataSymbol = Symbol('jymfony.reflect.inline');
 
-function docblockGetter(classId, memberIndex) {
-    const data = getInternalReflectionData(classId);
+/**
+ * Reflection data embedded into the compiled code takes precedence over the registry.
+ * It is pending until the class is decorated, then kept along with its reflection data.
+ *
+ * @param {string} classId
+ */
+function getData(classId) {
+    const c = reflectionDataMap.get(classId);
+    const data =
+        c !== void 0 && c[inlineDataSymbol] !== void 0
+            ? c[inlineDataSymbol]
+            : pendingInlineData.get(classId);
+
+    return data !== void 0 ? data : getInternalReflectionData(classId);
+}
+
+/**
+ * @param {string} classId
+ * @param {number} memberIndex
+ */
+function getMember(classId, memberIndex) {
+    const data = getData(classId);
     if (data === void 0) {
-        return null;
+        return undefined;
+    }
+
+    return data.members.find((m) => m.index === memberIndex);
+}
+
+/**
+ * Converts a member key into the name reported by the decorator context.
+ */
+function memberName(key) {
+    if (key === void 0 || key === null) {
+        return undefined;
     }
 
-    const member = data.members[memberIndex];
+    if (key.type === 'symbol') {
+        const wellKnown = key.value.startsWith('Symbol.')
+            ? Symbol[key.value.substring(7)]
+            : void 0;
+
+        return typeof wellKnown === 'symbol' ? wellKnown : Symbol.for(key.value);
+    }
+
+    return key.value;
+}
+
+function docblockGetter(classId, memberIndex) {
+    const member = getMember(classId, memberIndex);
+    if (member === void 0) {
+        return null;
+    }
 
     return member.docblock || null;
 }
 
+function parsedDocblockGetter(classId, memberIndex) {
+    const member = getMember(classId, memberIndex);
+    if (member === void 0) {
+        return null;
+    }
+
+    return member.parsedDocblock || null;
+}
+
+/**
+ * Reads a TypeScript annotation or modifier of a member: `type`, `returnType`,
+ * `accessibility` or `readonly`.
+ */
+function typeInfo(classId, memberIndex, property) {
+    const member = getMember(classId, memberIndex);
+    if (member === void 0 || member[property] === void 0) {
+        return null;
+    }
+
+    return member[property];
+}
+
 function parametersGetter(classId, memberIndex) {
-    const data = getInternalReflectionData(classId);
-    if (data === void 0) {
+    const member = getMember(classId, memberIndex);
+    if (member === void 0 || !member.params) {
         return [];
     }
 
-    const member = data.members[memberIndex];
-
     return member.params.map((p) => {
         const { scalarDefault, ...parameter } = p;
         if (scalarDefault) {
@@ -59,8 +127,17 @@ function parametersGetter(classId, memberIndex) {
 /**
  * @param {string} classId
  * @param {number|undefined} memberIndex
+ * @param {object|undefined} data Reflection data inlined by the compiler
  */
-exports._ = function __jymfony_reflect(classId, memberIndex = undefined) {
+exports._ = function __jymfony_reflect(
+    classId,
+    memberIndex = undefined,
+    data = undefined,
+) {
+    if (data !== void 0) {
+        pendingInlineData.set(classId, data);
+    }
+
     return (value, context) => {
         const c = (() => {
             const d = reflectionDataMap.get(classId);
@@ -68,12 +145,13 @@ exports._ = function __jymfony_reflect(classId, memberIndex = undefined) {
                 return d;
             }
 
-            const data = getInternalReflectionData(classId);
+            const data = getData(classId);
             if (data === void 0) {
                 return {
                     fqcn: context.name,
                     className: context.name,
                     members: [],
+                    implements: [],
                 };
             }
 
@@ -96,12 +174,27 @@ exports._ = function __jymfony_reflect(classId, memberIndex = undefined) {
                     get docblock() {
                         return docblockGetter(classId, memberIndex);
                     },
+                    get parsedDocblock() {
+                        return parsedDocblockGetter(classId, memberIndex);
+                    },
                     get parameters() {
                         return parametersGetter(classId, memberIndex);
                     },
+                    get accessibility() {
+                        return typeInfo(classId, memberIndex, 'accessibility');
+                    },
                 });
             }
 
+            const inline = pendingInlineData.get(classId);
+            if (inline !== void 0) {
+                Object.defineProperty(c, inlineDataSymbol, {
+                    value: inline,
+                    configurable: true,
+                });
+                pendingInlineData.delete(classId);
+            }
+
             reflectionDataMap.set(classId, c);
             return;
         }
@@ -125,9 +218,18 @@ exports._ = function __jymfony_reflect(classId, memberIndex = undefined) {
                 get parameters() {
                     return parametersGetter(classId, memberIndex);
                 },
+                get returnType() {
+                    return typeInfo(classId, memberIndex, 'returnType');
+                },
+                get accessibility() {
+                    return typeInfo(classId, memberIndex, 'accessibility');
+                },
                 get docblock() {
                     return docblockGetter(classId, memberIndex);
                 },
+                get parsedDocblock() {
+                    return parsedDocblockGetter(classId, memberIndex);
+                },
             });
         }
 
@@ -139,9 +241,21 @@ exports._ = function __jymfony_reflect(classId, memberIndex = undefined) {
                 static: context.static,
                 private: context.private,
                 access: context.access,
+                get type() {
+                    return typeInfo(classId, memberIndex, 'type');
+                },
+                get accessibility() {
+                    return typeInfo(classId, memberIndex, 'accessibility');
+                },
+                get readonly() {
+                    return typeInfo(classId, memberIndex, 'readonly');
+                },
                 get docblock() {
                     return docblockGetter(classId, memberIndex);
                 },
+                get parsedDocblock() {
+                    return parsedDocblockGetter(classId, memberIndex);
+                },
             });
         }
 
@@ -149,6 +263,60 @@ exports._ = function __jymfony_reflect(classId, memberIndex = undefined) {
     };
 };
 
+/**
+ * Builds the reflection data of a class which has not been evaluated yet,
+ * from the member descriptors collected at compile time.
+ *
+ * @param {string} classId
+ */
+function describeClass(classId) {
+    const data = getData(classId);
+    if (data === void 0) {
+        return undefined;
+    }
+
+    return {
+        ...data,
+        members: data.members.map((m) => {
+            const member = {
+                memberIndex: m.index,
+                kind: m.kind === 'constructor' ? 'method' : m.kind,
+                name: memberName(m.key),
+                static: m.static,
+                private: m.private,
+                computed: m.computed,
+                get type() {
+                    return typeInfo(classId, m.index, 'type');
+                },
+                get returnType() {
+                    return typeInfo(classId, m.index, 'returnType');
+                },
+                get accessibility() {
+                    return typeInfo(classId, m.index, 'accessibility');
+                },
+                get readonly() {
+                    return typeInfo(classId, m.index, 'readonly');
+                },
+                get docblock() {
+                    return docblockGetter(classId, m.index);
+                },
+                get parsedDocblock() {
+                    return parsedDocblockGetter(classId, m.index);
+                },
+            };
+
+            if (m.params) {
+                Object.defineProperty(member, 'parameters', {
+                    enumerable: true,
+                    get: () => parametersGetter(classId, m.index),
+                });
+            }
+
+            return member;
+        }),
+    };
+}
+
 exports.getReflectionData = function getReflectionData(classIdOrValue) {
     if (classIdOrValue === void 0 || classIdOrValue === null) {
         return undefined;
@@ -162,5 +330,10 @@ exports.getReflectionData = function getReflectionData(classIdOrValue) {
         classIdOrValue = metadata[reflectionSymbol];
     }
 
-    return reflectionDataMap.get(classIdOrValue);
+    const data = reflectionDataMap.get(classIdOrValue);
+    if (data === void 0 && typeof classIdOrValue === 'string') {
+        return describeClass(classIdOrValue);
+    }
+
+    return data;
 };
@@ -0,0 +1,243 @@
+use jymfony_compiler::parser::{
+    CompileOptions, JsxRuntime, ModuleKind, ReflectionMode, SourceMapMode, SourceMapOptions,
+};
+use std::path::PathBuf;
+use swc_ecma_ast::EsVersion;
+
+pub const USAGE: &str = "Usage: jymfony-compiler [OPTIONS] --out-dir <DIR> <INPUT>
+
+Compiles a file or a directory tree into the output directory.
+
+Options:
+  -o, --out-dir <DIR>            Directory where the compiled files are written
+      --namespace <NAMESPACE>    Namespace of the compiled classes
+      --debug                    Keeps the assertion calls
+      --as-function              Wraps the compiled code into a function
+      --as-module                Keeps the ES module syntax
+      --module-kind <KIND>       Compiles the sources as `module` or `script`
+      --target <VERSION>         ECMAScript version to target (es2017 to es2022, esnext)
+      --minify                   Compresses and mangles the compiled code
+      --deterministic-ids        Derives the class ids from their file and position
+      --reflection <MODE>        `registry` (default) or `inline` reflection metadata
+      --emit-decorator-metadata  Emits the design-time types of decorated TS classes
+      --implements-mixins        Applies the implemented TS interfaces as mixins
+      --jsx-runtime <RUNTIME>    JSX runtime: `classic` or `automatic`
+      --jsx-pragma <PRAGMA>      Function used by the classic runtime
+      --jsx-pragma-frag <FRAG>   Fragment used by the classic runtime
+      --jsx-import-source <SRC>  Module importing the automatic runtime
+      --source-map <MODE>        `external` (default), `inline`, `hidden` or `none`
+      --source-root <ROOT>       Source root recorded in the source maps
+      --sources-content          Embeds the original sources into the source maps
+      --error-format <FORMAT>    Diagnostics format: `human` (default) or `json`
+  -h, --help                     Prints this help";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorFormat {
+    Human,
+    Json,
+}
+
+pub struct Args {
+    pub input: PathBuf,
+    pub out_dir: PathBuf,
+    pub error_format: ErrorFormat,
+    pub options: CompileOptions,
+}
+
+fn value(
+    flag: &str,
+    inline: Option<String>,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<String, String> {
+    inline
+        .or_else(|| args.next())
+        .ok_or_else(|| format!("missing value for {}", flag))
+}
+
+fn parse_target(target: &str) -> Result<EsVersion, String> {
+    match target {
+        "es2017" => Ok(EsVersion::Es2017),
+        "es2018" => Ok(EsVersion::Es2018),
+        "es2019" => Ok(EsVersion::Es2019),
+        "es2020" => Ok(EsVersion::Es2020),
+        "es2021" => Ok(EsVersion::Es2021),
+        "es2022" => Ok(EsVersion::Es2022),
+        "esnext" => Ok(EsVersion::EsNext),
+        _ => Err(format!("unknown target \"{}\"", target)),
+    }
+}
+
+impl Args {
+    /// Parses the command line arguments (program name excluded).
+    /// Returns `None` if the help has been requested.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut args = args.into_iter();
+        let mut input = None;
+        let mut out_dir = None;
+        let mut error_format = ErrorFormat::Human;
+        let mut options = CompileOptions {
+            source_map: SourceMapOptions {
+                mode: SourceMapMode::External,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut jsx_runtime = None;
+        let mut pragma = None;
+        let mut pragma_frag = None;
+        let mut import_source = None;
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, v)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(v.to_string()))
+                }
+                _ => (arg, None),
+            };
+
+            match flag.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-o" | "--out-dir" => {
+                    out_dir = Some(PathBuf::from(value(&flag, inline, &mut args)?))
+                }
+                "--namespace" => options.namespace = Some(value(&flag, inline, &mut args)?),
+                "--debug" => options.debug = true,
+                "--as-function" => options.as_function = true,
+                "--as-module" => options.as_module = true,
+                "--minify" => options.minify = true,
+                "--deterministic-ids" => options.deterministic_ids = true,
+                "--emit-decorator-metadata" => options.emit_decorator_metadata = true,
+                "--implements-mixins" => options.implements_mixins = true,
+                "--sources-content" => options.source_map.sources_content = true,
+                "--source-root" => {
+                    options.source_map.source_root = Some(value(&flag, inline, &mut args)?)
+                }
+                "--target" => {
+                    options.target = Some(parse_target(&value(&flag, inline, &mut args)?)?)
+                }
+                "--reflection" => {
+                    options.reflection = match value(&flag, inline, &mut args)?.as_str() {
+                        "registry" => ReflectionMode::Registry,
+                        "inline" => ReflectionMode::Inline,
+                        mode => return Err(format!("unknown reflection mode \"{}\"", mode)),
+                    }
+                }
+                "--module-kind" => {
+                    options.module_kind = match value(&flag, inline, &mut args)?.as_str() {
+                        "module" => Some(ModuleKind::Module),
+                        "script" => Some(ModuleKind::Script),
+                        kind => return Err(format!("unknown module kind \"{}\"", kind)),
+                    }
+                }
+                "--source-map" => {
+                    options.source_map.mode = match value(&flag, inline, &mut args)?.as_str() {
+                        "inline" => SourceMapMode::Inline,
+                        "external" => SourceMapMode::External,
+                        "hidden" => SourceMapMode::Hidden,
+                        "none" => SourceMapMode::None,
+                        mode => return Err(format!("unknown source map mode \"{}\"", mode)),
+                    }
+                }
+                "--jsx-runtime" => jsx_runtime = Some(value(&flag, inline, &mut args)?),
+                "--jsx-pragma" => pragma = Some(value(&flag, inline, &mut args)?),
+                "--jsx-pragma-frag" => pragma_frag = Some(value(&flag, inline, &mut args)?),
+                "--jsx-import-source" => import_source = Some(value(&flag, inline, &mut args)?),
+                "--error-format" => {
+                    error_format = match value(&flag, inline, &mut args)?.as_str() {
+                        "human" => ErrorFormat::Human,
+                        "json" => ErrorFormat::Json,
+                        format => return Err(format!("unknown error format \"{}\"", format)),
+                    }
+                }
+                f if f.starts_with('-') && f.len() > 1 => {
+                    return Err(format!("unknown option {}", f))
+                }
+                _ if input.is_some() => return Err(format!("unexpected argument {}", flag)),
+                _ => input = Some(PathBuf::from(&flag)),
+            }
+        }
+
+        options.jsx = match jsx_runtime.as_deref() {
+            None | Some("classic") => JsxRuntime::Classic {
+                pragma,
+                pragma_frag,
+            },
+            Some("automatic") => JsxRuntime::Automatic { import_source },
+            Some(runtime) => return Err(format!("unknown JSX runtime \"{}\"", runtime)),
+        };
+
+        Ok(Some(Self {
+            input: input.ok_or("missing input file or directory")?,
+            out_dir: out_dir.ok_or("missing output directory")?,
+            error_format,
+            options,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Args, ErrorFormat};
+    use jymfony_compiler::parser::{JsxRuntime, ModuleKind, ReflectionMode, SourceMapMode};
+    use std::path::PathBuf;
+    use swc_ecma_ast::EsVersion;
+
+    fn parse(args: &[&str]) -> Result<Option<Args>, String> {
+        Args::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    pub fn should_parse_compile_options() {
+        let args = parse(&[
+            "src",
+            "-o",
+            "dist",
+            "--namespace=App.Entity",
+            "--module-kind",
+            "module",
+            "--target",
+            "es2020",
+            "--jsx-runtime=automatic",
+            "--jsx-import-source",
+            "preact",
+            "--minify",
+            "--reflection=inline",
+            "--emit-decorator-metadata",
+            "--implements-mixins",
+            "--error-format",
+            "json",
+        ])
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(args.input, PathBuf::from("src"));
+        assert_eq!(args.out_dir, PathBuf::from("dist"));
+        assert_eq!(args.error_format, ErrorFormat::Json);
+        assert_eq!(args.options.namespace.as_deref(), Some("App.Entity"));
+        assert_eq!(args.options.module_kind, Some(ModuleKind::Module));
+        assert_eq!(args.options.target, Some(EsVersion::Es2020));
+        assert_eq!(args.options.source_map.mode, SourceMapMode::External);
+        assert!(args.options.minify);
+        assert_eq!(args.options.reflection, ReflectionMode::Inline);
+        assert!(args.options.emit_decorator_metadata);
+        assert!(args.options.implements_mixins);
+        assert!(!args.options.debug);
+        assert!(matches!(
+            args.options.jsx,
+            JsxRuntime::Automatic { import_source: Some(ref s) } if s == "preact"
+        ));
+    }
+
+    #[test]
+    pub fn should_reject_invalid_arguments() {
+        assert!(parse(&["--help"]).unwrap().is_none());
+        assert!(parse(&["src"]).is_err());
+        assert!(parse(&["-o", "dist"]).is_err());
+        assert!(parse(&["src", "-o", "dist", "--unknown"]).is_err());
+        assert!(parse(&["src", "-o", "dist", "--target", "es5"]).is_err());
+        assert!(parse(&["src", "-o", "dist", "--reflection", "global"]).is_err());
+        assert!(parse(&["src", "other", "-o", "dist"]).is_err());
+        assert!(parse(&["src", "-o"]).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+mod args;
+
+use args::{Args, ErrorFormat, USAGE};
+use jymfony_compiler::parser::{CodeParser, SourceMapMode};
+use jymfony_compiler::{Diagnostic, Severity, SyntaxError};
+use std::fs;
+use std::io;
+use std::path::{absolute, Component, Path, PathBuf};
+use std::process::ExitCode;
+
+const EXTENSIONS: [&str; 8] = ["js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts"];
+
+enum Failure {
+    Syntax(Vec<Diagnostic>),
+    Other(String),
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let sources = match collect_sources(&args.input, &args.out_dir) {
+        Ok(sources) => sources,
+        Err(e) => {
+            report_failure(
+                &args,
+                &args.input,
+                Failure::Other(format!("cannot read input: {}", e)),
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failed = 0;
+    for (source, relative) in &sources {
+        match compile_file(&args, source, relative) {
+            Ok(warnings) => warnings.iter().for_each(|w| report(&args, w)),
+            Err(failure) => {
+                failed += 1;
+                report_failure(&args, source, failure);
+            }
+        }
+    }
+
+    if failed > 0 {
+        if args.error_format == ErrorFormat::Human {
+            eprintln!("{} of {} files failed to compile", failed, sources.len());
+        }
+
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Lists the files to compile along with their path relative to the input.
+/// Symlinked directories are not followed (they may form cycles) and the output
+/// directory is skipped if nested in the input, not to compile the compiled files again.
+fn collect_sources(input: &Path, out_dir: &Path) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    if !input.is_dir() {
+        let relative = input.file_name().map(PathBuf::from).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "input is not a file name")
+        })?;
+
+        return Ok(vec![(input.to_path_buf(), relative)]);
+    }
+
+    let out_dir = fs::canonicalize(out_dir).ok();
+    let mut sources = vec![];
+    let mut dirs = vec![input.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                if out_dir.is_none() || fs::canonicalize(&path).ok() != out_dir {
+                    dirs.push(path);
+                }
+            } else if is_source(&path) && path.is_file() {
+                let relative = path.strip_prefix(input).unwrap().to_path_buf();
+                sources.push((path, relative));
+            }
+        }
+    }
+
+    sources.sort();
+    Ok(sources)
+}
+
+fn is_source(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let is_declaration = [".d.ts", ".d.mts", ".d.cts"]
+        .iter()
+        .any(|ext| name.ends_with(ext));
+
+    !is_declaration
+        && path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| EXTENSIONS.contains(&e))
+}
+
+/// The path of the compiled file: typescript and JSX sources are compiled to javascript.
+fn output_path(relative: &Path) -> PathBuf {
+    match relative.extension().and_then(|e| e.to_str()) {
+        Some("ts" | "tsx" | "jsx") => relative.with_extension("js"),
+        Some("mts") => relative.with_extension("mjs"),
+        Some("cts") => relative.with_extension("cjs"),
+        _ => relative.to_path_buf(),
+    }
+}
+
+/// The path of `path` relative to the `base` directory, with forward slashes
+/// as source maps expect.
+fn relative_path(path: &Path, base: &Path) -> io::Result<String> {
+    let path = absolute(path)?;
+    let base = absolute(base)?;
+    let common = path
+        .components()
+        .zip(base.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = base
+        .components()
+        .skip(common)
+        .map(|_| Component::ParentDir)
+        .collect::<PathBuf>();
+    relative.extend(path.components().skip(common));
+
+    Ok(relative.to_string_lossy().replace('\\', "/"))
+}
+
+fn compile_file(args: &Args, source: &Path, relative: &Path) -> Result<Vec<Diagnostic>, Failure> {
+    let filename = source.to_string_lossy();
+    let code = fs::read_to_string(source)
+        .map_err(|e| Failure::Other(format!("cannot read file: {}", e)))?;
+
+    let out_file = args.out_dir.join(output_path(relative));
+    let mut options = args.options.clone();
+    if options.source_map.file.is_none() {
+        options.source_map.file = out_file
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string());
+    }
+
+    // The sources of the maps are resolved from the location of the compiled file.
+    if options.source_map.source_path.is_none() {
+        let out_file_dir = out_file.parent().unwrap_or(&args.out_dir);
+        options.source_map.source_path = relative_path(source, out_file_dir).ok();
+    }
+
+    let program = code
+        .parse_program(Some(&filename))
+        .map_err(|e| match e.downcast_ref::<SyntaxError>() {
+            Some(syntax_error) => Failure::Syntax(syntax_error.diagnostics().to_vec()),
+            None => Failure::Other(e.to_string()),
+        })?;
+
+    let write_map = matches!(
+        options.source_map.mode,
+        SourceMapMode::External | SourceMapMode::Hidden
+    );
+    let result = program
+        .compile(options)
+        .map_err(|e| Failure::Other(e.to_string()))?;
+
+    let write = |path: &Path, contents: &str| {
+        path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(path, contents))
+            .map_err(|e| Failure::Other(format!("cannot write {}: {}", path.display(), e)))
+    };
+
+    write(&out_file, &result.code)?;
+    if let Some(map) = result.source_map.as_deref().filter(|_| write_map) {
+        let mut map_file = out_file.into_os_string();
+        map_file.push(".map");
+        write(Path::new(&map_file), map)?;
+    }
+
+    Ok(result.warnings)
+}
+
+fn report(args: &Args, diagnostic: &Diagnostic) {
+    match args.error_format {
+        ErrorFormat::Json => {
+            eprintln!("{}", serde_json::to_string(diagnostic).unwrap_or_default());
+        }
+        ErrorFormat::Human => {
+            eprintln!(
+                "{}:{}:{}: {}: {} [{}]",
+                diagnostic.filename.as_deref().unwrap_or("<unknown>"),
+                diagnostic.line,
+                diagnostic.column,
+                match diagnostic.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                },
+                diagnostic.message,
+                diagnostic.kind
+            );
+
+            if let Some(code_frame) = &diagnostic.code_frame {
+                eprintln!("{}\n", code_frame);
+            }
+        }
+    }
+}
+
+fn report_failure(args: &Args, source: &Path, failure: Failure) {
+    match failure {
+        Failure::Syntax(diagnostics) => diagnostics.iter().for_each(|d| report(args, d)),
+        Failure::Other(message) => match args.error_format {
+            ErrorFormat::Json => eprintln!(
+                "{}",
+                serde_json::json!({
+                    "filename": source.to_string_lossy(),
+                    "message": message,
+                    "severity": "error",
+                })
+            ),
+            ErrorFormat::Human => eprintln!("{}: error: {}", source.display(), message),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect_sources, is_source, output_path, relative_path};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    pub fn should_select_and_rename_sources() {
+        assert!(is_source(Path::new("src/a.js")));
+        assert!(is_source(Path::new("src/a.mts")));
+        assert!(!is_source(Path::new("src/a.d.ts")));
+        assert!(!is_source(Path::new("src/a.json")));
+
+        assert_eq!(output_path(Path::new("a/b.ts")), PathBuf::from("a/b.js"));
+        assert_eq!(output_path(Path::new("a/b.tsx")), PathBuf::from("a/b.js"));
+        assert_eq!(output_path(Path::new("a/b.cts")), PathBuf::from("a/b.cjs"));
+        assert_eq!(output_path(Path::new("a/b.mjs")), PathBuf::from("a/b.mjs"));
+    }
+
+    #[test]
+    pub fn should_compute_source_paths_relative_to_the_output() {
+        assert_eq!(
+            relative_path(Path::new("src/a/b.ts"), Path::new("dist/a")).unwrap(),
+            "../../src/a/b.ts"
+        );
+        assert_eq!(
+            relative_path(Path::new("src/b.ts"), Path::new("src/dist")).unwrap(),
+            "../b.ts"
+        );
+    }
+
+    #[test]
+    pub fn should_skip_the_output_directory_and_symlinked_directories() {
+        let root = tempfile::tempdir().unwrap();
+        let input = root.path();
+        fs::create_dir_all(input.join("lib")).unwrap();
+        fs::create_dir_all(input.join("dist")).unwrap();
+        fs::write(input.join("a.ts"), "").unwrap();
+        fs::write(input.join("lib/b.js"), "").unwrap();
+        fs::write(input.join("dist/a.js"), "").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(input, input.join("lib/loop")).unwrap();
+
+        let sources = collect_sources(input, &input.join("dist")).unwrap();
+        let relative = sources.into_iter().map(|(_, r)| r).collect::<Vec<_>>();
+        assert_eq!(
+            relative,
+            vec![PathBuf::from("a.ts"), PathBuf::from("lib/b.js")]
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! Content-addressed cache of compiled programs.
+//!
+//! Compilations are keyed by a hash of the source, the filename and the compile
+//! options. Entries are kept in memory and, optionally, stored on disk along with
+//! the reflection data of the compiled classes, so that a cache hit restores the
+//! same state a compilation would produce.
+
+use crate::parser::{CodeParser, CompileOptions, CompileResult};
+use crate::reflection::{get_reflection_data, register_file, ReflectionData};
+use crate::stack::get_source_map;
+use crate::Diagnostic;
+use moka::sync::Cache;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// The version of the cache key and of the stored entries format.
+/// Must be bumped whenever any of them changes, invalidating the existing entries.
+const CACHE_VERSION: u32 = 1;
+
+/// Distinguishes the temporary files written concurrently by the same process.
+static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone)]
+struct CacheEntry {
+    result: CompileResult,
+    reflection: Vec<(Uuid, Arc<ReflectionData>)>,
+    /// The source map registered for stack traces, which may differ from the returned one
+    /// (ie: its sources are not relative to the output).
+    registered_source_map: Option<String>,
+}
+
+/// The on-disk representation of a cache entry.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredEntry {
+    code: String,
+    source_map: Option<String>,
+    dependencies: Vec<String>,
+    warnings: Vec<Diagnostic>,
+    classes: Vec<(String, ReflectionData)>,
+    registered_source_map: Option<String>,
+}
+
+impl From<StoredEntry> for CacheEntry {
+    fn from(value: StoredEntry) -> Self {
+        let reflection = value
+            .classes
+            .into_iter()
+            .filter_map(|(id, data)| Some((Uuid::parse_str(&id).ok()?, Arc::new(data))))
+            .collect::<Vec<_>>();
+
+        Self {
+            result: CompileResult {
+                code: value.code,
+                source_map: value.source_map,
+                class_ids: reflection.iter().map(|(id, _)| *id).collect(),
+                dependencies: value.dependencies,
+                warnings: value.warnings,
+            },
+            reflection,
+            registered_source_map: value.registered_source_map,
+        }
+    }
+}
+
+pub struct CompileCache {
+    memory: Cache<String, CacheEntry>,
+    directory: Option<PathBuf>,
+}
+
+impl CompileCache {
+    /// Creates a cache holding up to `capacity` compiled programs in memory.
+    /// If a directory is given, entries are also stored on disk and survive restarts.
+    pub fn new(capacity: u64, directory: Option<PathBuf>) -> Self {
+        Self {
+            memory: Cache::new(capacity),
+            directory,
+        }
+    }
+
+    /// Computes the cache key of a compilation.
+    fn key(source: &str, filename: Option<&str>, opts: &CompileOptions) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(env!("CARGO_PKG_VERSION"));
+        hasher.update(b"\0");
+        hasher.update(CACHE_VERSION.to_le_bytes());
+        hasher.update(b"\0");
+        hasher.update(serde_json::to_vec(opts).expect("compile options are serializable"));
+        hasher.update(b"\0");
+        hasher.update(filename.unwrap_or_default());
+        hasher.update(b"\0");
+        hasher.update(source);
+
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn entry_path(directory: &Path, key: &str) -> PathBuf {
+        directory.join(&key[..2]).join(format!("{}.json", key))
+    }
+
+    fn load(&self, key: &str) -> Option<CacheEntry> {
+        if let Some(entry) = self.memory.get(key) {
+            return Some(entry);
+        }
+
+        let path = Self::entry_path(self.directory.as_deref()?, key);
+        let contents = fs::read(path).ok()?;
+        let entry = CacheEntry::from(serde_json::from_slice::<StoredEntry>(&contents).ok()?);
+        self.memory.insert(key.to_string(), entry.clone());
+
+        Some(entry)
+    }
+
+    fn store(&self, key: &str, entry: CacheEntry) {
+        if let Some(directory) = self.directory.as_deref() {
+            // Failing to write the cache must not fail the compilation.
+            let _ = Self::write(directory, key, &entry);
+        }
+
+        self.memory.insert(key.to_string(), entry);
+    }
+
+    fn write(directory: &Path, key: &str, entry: &CacheEntry) -> anyhow::Result<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct StoredEntryRef<'a> {
+            code: &'a str,
+            source_map: Option<&'a str>,
+            dependencies: &'a [String],
+            warnings: &'a [Diagnostic],
+            classes: Vec<(String, &'a ReflectionData)>,
+            registered_source_map: Option<&'a str>,
+        }
+
+        let result = &entry.result;
+        let contents = serde_json::to_vec(&StoredEntryRef {
+            code: &result.code,
+            source_map: result.source_map.as_deref(),
+            dependencies: &result.dependencies,
+            warnings: &result.warnings,
+            classes: entry
+                .reflection
+                .iter()
+                .map(|(id, data)| (id.to_string(), data.as_ref()))
+                .collect(),
+            registered_source_map: entry.registered_source_map.as_deref(),
+        })?;
+
+        let path = Self::entry_path(directory, key);
+        fs::create_dir_all(path.parent().unwrap())?;
+
+        // Write to a temporary file first, readers never see partial entries.
+        let tmp_path = path.with_extension(format!(
+            "json.{}.{}",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp_path, contents)?;
+        fs::rename(tmp_path, path)?;
+
+        Ok(())
+    }
+
+    /// Registers the classes and the source map of a cached compilation,
+    /// as the compilation itself would have done.
+    fn restore(entry: &CacheEntry, filename: Option<&str>) {
+        let source_map = entry
+            .registered_source_map
+            .as_deref()
+            .and_then(|s| sourcemap::SourceMap::from_slice(s.as_bytes()).ok());
+
+        register_file(filename, entry.reflection.clone(), source_map);
+    }
+
+    /// Compiles the given source, returning the cached result if the same
+    /// source has already been compiled with the same filename and options.
+    pub fn compile(
+        &self,
+        source: &str,
+        filename: Option<&str>,
+        opts: CompileOptions,
+    ) -> anyhow::Result<CompileResult> {
+        let key = Self::key(source, filename, &opts);
+        if let Some(entry) = self.load(&key) {
+            Self::restore(&entry, filename);
+            return Ok(entry.result);
+        }
+
+        let program = source.parse_program(filename)?;
+        let result = program.compile(opts)?;
+        let reflection = result
+            .class_ids
+            .iter()
+            .filter_map(|id| Some((*id, get_reflection_data(id)?)))
+            .collect();
+        let registered_source_map = filename.and_then(get_source_map).and_then(|map| {
+            let mut buf = vec![];
+            map.to_writer(&mut buf).ok()?;
+            String::from_utf8(buf).ok()
+        });
+
+        self.store(
+            &key,
+            CacheEntry {
+                result: result.clone(),
+                reflection,
+                registered_source_map,
+            },
+        );
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompileCache;
+    use crate::parser::{CodeParser, CompileOptions, SourceMapMode, SourceMapOptions};
+    use crate::reflection::get_reflection_data;
+    use crate::stack::{get_source_map, remap_stack_trace, Frame};
+    use crate::testing::uuid::reset_test_uuid;
+
+    #[test]
+    pub fn should_return_cached_compilations() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let dir = tempfile::tempdir()?;
+        let code = "/** Docblock */\nexport default class Cached {}\n";
+        let cache = CompileCache::new(16, Some(dir.path().to_path_buf()));
+
+        let compiled = cache.compile(code, Some("cached.js"), Default::default())?;
+        let hit = cache.compile(code, Some("cached.js"), Default::default())?;
+        assert_eq!(hit.code, compiled.code);
+        assert_eq!(hit.class_ids, compiled.class_ids);
+
+        // A cold cache reads the entry stored on disk.
+        let cache = CompileCache::new(16, Some(dir.path().to_path_buf()));
+        let hit = cache.compile(code, Some("cached.js"), Default::default())?;
+        assert_eq!(hit.code, compiled.code);
+        assert_eq!(hit.class_ids, compiled.class_ids);
+        assert_eq!(hit.source_map, compiled.source_map);
+
+        let data = get_reflection_data(&hit.class_ids[0]).unwrap();
+        assert_eq!(data.name, "Cached");
+
+        // Different options are a different entry.
+        let namespaced = cache.compile(
+            code,
+            Some("cached.js"),
+            CompileOptions {
+                namespace: Some("App".to_string()),
+                ..Default::default()
+            },
+        )?;
+        assert_ne!(namespaced.class_ids, compiled.class_ids);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_register_the_compiled_source_map_on_cache_hits() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let code = "export function fail() {\n    throw new Error('fail');\n}\n";
+        let opts = || CompileOptions {
+            source_map: SourceMapOptions {
+                mode: SourceMapMode::External,
+                source_path: Some("../src/remap.js".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let registered_map = || {
+            let mut buf = vec![];
+            get_source_map("remap.js").unwrap().to_writer(&mut buf).unwrap();
+            String::from_utf8(buf).unwrap()
+        };
+
+        let compiled = CompileCache::new(16, Some(dir.path().to_path_buf())).compile(
+            code,
+            Some("remap.js"),
+            opts(),
+        )?;
+
+        let (line_no, line) = compiled
+            .code
+            .lines()
+            .enumerate()
+            .find(|(_, l)| l.contains("throw"))
+            .unwrap();
+        let remap = || {
+            let frame = Frame {
+                filename: Some("remap.js".to_string()),
+                line_no: line_no as u32 + 1,
+                col_no: line.find("throw").unwrap() as u32 + 1,
+                function_name: Some("fail".to_string()),
+                method_name: None,
+                type_name: None,
+                is_native: false,
+                is_top_level: true,
+                is_constructor: false,
+                is_async: false,
+                is_promise_all: false,
+                promise_index: 0,
+                string_repr: "fail (remap.js)".to_string(),
+            };
+
+            remap_stack_trace("Error: fail", &[frame], None)
+        };
+
+        let on_miss = remap();
+        let map_on_miss = registered_map();
+        assert!(on_miss.contains("remap.js:2:5"));
+        assert!(compiled.source_map.unwrap().contains("../src/remap.js"));
+        assert!(!map_on_miss.contains("../src/remap.js"));
+
+        // A cold cache restores the entry stored on disk, replacing the source map
+        // registered by another compilation of the same file.
+        "export const a = 1;"
+            .parse_program(Some("remap.js"))?
+            .compile(Default::default())?;
+        assert_ne!(registered_map(), map_on_miss);
+
+        CompileCache::new(16, Some(dir.path().to_path_buf())).compile(
+            code,
+            Some("remap.js"),
+            opts(),
+        )?;
+
+        assert_eq!(remap(), on_miss);
+        assert_eq!(registered_map(), map_on_miss);
+
+        Ok(())
+    }
+}
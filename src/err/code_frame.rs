@@ -0,0 +1,144 @@
+/// Number of lines shown before and after the offending one.
+const CONTEXT_LINES: usize = 2;
+
+/// Renders a code frame pointing to the given byte range of `src`.
+///
+/// The offending line is marked in the gutter and the range is underlined
+/// with carets. Ranges spanning multiple lines are underlined up to the end
+/// of the first line. Tabs are mirrored in the underline so that the carets
+/// stay aligned whatever the tab width of the terminal is.
+pub(crate) fn render_code_frame(src: &str, start: usize, end: usize) -> String {
+    let start = start.min(src.len());
+    let end = end.max(start).min(src.len());
+
+    let line_idx = src[..start].matches('\n').count();
+    let mut lines = src.split('\n').collect::<Vec<_>>();
+    if lines.len() > 1 && lines.last() == Some(&"") && line_idx < lines.len() - 1 {
+        lines.pop();
+    }
+
+    let first = line_idx.saturating_sub(CONTEXT_LINES);
+    let last = (line_idx + CONTEXT_LINES).min(lines.len() - 1);
+    let gutter_width = (last + 1).to_string().len();
+
+    let line_start = src[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let error_line = lines[line_idx].trim_end_matches('\r');
+    let line_end = line_start + error_line.len();
+
+    let padding = src[line_start..start]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect::<String>();
+    let underline_len = src[start..end.min(line_end).max(start)]
+        .chars()
+        .count()
+        .max(1);
+
+    let mut frame = vec![];
+    for (idx, line) in lines.iter().enumerate().take(last + 1).skip(first) {
+        let line = line.trim_end_matches('\r');
+        let marker = if idx == line_idx { '>' } else { ' ' };
+        let mut rendered = format!("{} {:>width$} |", marker, idx + 1, width = gutter_width);
+        if !line.is_empty() {
+            rendered.push(' ');
+            rendered.push_str(line);
+        }
+
+        frame.push(rendered);
+
+        if idx == line_idx {
+            frame.push(format!(
+                "  {:width$} | {}{}",
+                "",
+                padding,
+                "^".repeat(underline_len),
+                width = gutter_width
+            ));
+        }
+    }
+
+    frame.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_code_frame;
+
+    #[test]
+    pub fn should_render_context_lines() {
+        let src = "const a = 1;\nnew class ext impl test {[]}\nconst b = 2;\n";
+
+        assert_eq!(
+            render_code_frame(src, 27, 31),
+            r#"  1 | const a = 1;
+> 2 | new class ext impl test {[]}
+    |               ^^^^
+  3 | const b = 2;"#
+        );
+    }
+
+    #[test]
+    pub fn should_align_gutter_and_limit_context() {
+        let src = (1..=12)
+            .map(|i| format!("line{}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let start = src.find("line10").unwrap();
+
+        assert_eq!(
+            render_code_frame(&src, start, start + 6),
+            r#"   8 | line8
+   9 | line9
+> 10 | line10
+     | ^^^^^^
+  11 | line11
+  12 | line12"#
+        );
+    }
+
+    #[test]
+    pub fn should_keep_tabs_aligned() {
+        let src = "function x() {\n\treturn ?;\n}";
+
+        assert_eq!(
+            render_code_frame(src, 23, 24),
+            "  1 | function x() {\n> 2 | \treturn ?;\n    | \t       ^\n  3 | }"
+        );
+    }
+
+    #[test]
+    pub fn should_count_multibyte_chars_once() {
+        let src = "const ñandú = 'ü' +;\n";
+        let start = src.find(';').unwrap();
+
+        assert_eq!(start, 22);
+        assert_eq!(
+            render_code_frame(src, start, start + 1),
+            r#"> 1 | const ñandú = 'ü' +;
+    |                    ^"#
+        );
+    }
+
+    #[test]
+    pub fn should_underline_multiline_spans_up_to_line_end() {
+        let src = "let x = `abc\ndef";
+
+        assert_eq!(
+            render_code_frame(src, 8, src.len()),
+            r#"> 1 | let x = `abc
+    |         ^^^^
+  2 | def"#
+        );
+    }
+
+    #[test]
+    pub fn should_render_a_single_caret_for_empty_spans() {
+        let src = "foo(";
+
+        assert_eq!(
+            render_code_frame(src, 4, 4),
+            r#"> 1 | foo(
+    |     ^"#
+        );
+    }
+}
@@ -0,0 +1,214 @@
+mod code_frame;
+
+use code_frame::render_code_frame;
+use core::fmt::{Debug, Display, Formatter};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use swc_common::source_map::Pos;
+use swc_common::{SourceFile, SourceMap, Span, Spanned};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A byte range in the original source.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A single problem found while processing a source file.
+/// Lines are 1-based, columns are 1-based and counted in UTF-16 code units.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub filename: Option<String>,
+    pub span: SourceSpan,
+    pub line: usize,
+    pub column: usize,
+    pub kind: String,
+    pub message: String,
+    pub severity: Severity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_frame: Option<String>,
+}
+
+impl Diagnostic {
+    fn new(
+        source_file: &SourceFile,
+        span: Span,
+        filename: Option<&str>,
+        kind: String,
+        message: String,
+        severity: Severity,
+    ) -> Self {
+        let src = source_file.src.as_str();
+        let start = get_source_offset(source_file, span.lo.to_usize());
+        let end = get_source_offset(source_file, span.hi.to_usize()).max(start);
+
+        let line_start = get_line_start_byte_pos(src, start);
+        let line = src[..line_start].matches('\n').count() + 1;
+        let column = src[line_start..start].encode_utf16().count() + 1;
+
+        Self {
+            filename: filename.map(|f| f.to_string()),
+            span: SourceSpan { start, end },
+            line,
+            column,
+            kind,
+            message,
+            severity,
+            code_frame: Some(render_code_frame(src, start, end)),
+        }
+    }
+
+    pub(crate) fn from_parser_error(
+        e: &swc_ecma_parser::error::Error,
+        source_file: &SourceFile,
+        filename: Option<&str>,
+    ) -> Self {
+        Self::new(
+            source_file,
+            e.span(),
+            filename,
+            get_error_kind_name(e.kind()),
+            e.kind().msg().to_string(),
+            Severity::Error,
+        )
+    }
+
+    /// Creates a warning pointing to the given span of a compiled file.
+    pub(crate) fn warning(
+        source_map: &SourceMap,
+        span: Span,
+        filename: Option<&str>,
+        kind: &str,
+        message: String,
+    ) -> Self {
+        let source_file = source_map.lookup_byte_offset(span.lo).sf;
+
+        Self::new(
+            &source_file,
+            span,
+            filename,
+            kind.to_string(),
+            message,
+            Severity::Warning,
+        )
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}: {} on line {}, column {}",
+            match self.severity {
+                Severity::Error => "SyntaxError",
+                Severity::Warning => "Warning",
+            },
+            self.message,
+            self.line,
+            self.column
+        )
+    }
+}
+
+/// Converts an absolute position in the source map into a byte offset
+/// into the source file text, clamped to a valid char boundary.
+fn get_source_offset(source_file: &SourceFile, pos: usize) -> usize {
+    let src = source_file.src.as_str();
+    let mut offset = pos
+        .saturating_sub(source_file.start_pos.to_usize())
+        .min(src.len());
+
+    while !src.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    offset
+}
+
+/// Extracts the variant name of the swc error kind (ie: "Expected", "LegacyOctal").
+fn get_error_kind_name(kind: &swc_ecma_parser::error::SyntaxError) -> String {
+    let debug = format!("{:?}", kind);
+    debug
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[derive(Debug)]
+pub struct SyntaxError {
+    msg: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl SyntaxError {
+    fn new(msg: &str) -> Self {
+        Self {
+            msg: msg.to_string(),
+            diagnostics: vec![],
+        }
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// The rendered code frame of the first error in source order, if any.
+    pub fn code_frame(&self) -> Option<&str> {
+        self.diagnostics
+            .iter()
+            .find(|d| d.severity == Severity::Error)
+            .and_then(|d| d.code_frame.as_deref())
+    }
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl Error for SyntaxError {}
+
+fn get_line_start_byte_pos(text: &str, pos: usize) -> usize {
+    let text_bytes = text.as_bytes();
+    for i in (0..pos).rev() {
+        if text_bytes.get(i) == Some(&(b'\n')) {
+            return i + 1;
+        }
+    }
+
+    0
+}
+
+impl SyntaxError {
+    /// Builds a syntax error carrying a diagnostic for each of the given parser errors.
+    /// The diagnostics are sorted in source order; the error message and the
+    /// code frame are both built from the first one.
+    pub(crate) fn from_parser_errors(
+        errors: &[swc_ecma_parser::error::Error],
+        source_file: &SourceFile,
+        filename: Option<&str>,
+    ) -> Self {
+        debug_assert!(!errors.is_empty());
+
+        let mut diagnostics = errors
+            .iter()
+            .map(|e| Diagnostic::from_parser_error(e, source_file, filename))
+            .collect::<Vec<_>>();
+        diagnostics.sort_by_key(|d| d.span.start);
+
+        let mut error = Self::new(&diagnostics.first().unwrap().to_string());
+        error.diagnostics = diagnostics;
+
+        error
+    }
+}
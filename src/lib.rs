@@ -1,16 +1,36 @@
+//! The Jymfony compiler.
+//!
+//! Compiles javascript and typescript sources, attaching reflection metadata to
+//! the declared classes and remapping stack traces through the generated source maps.
+//!
+//! ```
+//! use jymfony_compiler::parser::{CodeParser, CompileOptions};
+//!
+//! let program = "export default class Foo {}".parse_program(Some("foo.js"))?;
+//! let result = program.compile(CompileOptions::default())?;
+//!
+//! let class_id = result.class_ids[0];
+//! let reflection = jymfony_compiler::reflection::get_reflection_data(&class_id).unwrap();
+//! assert_eq!(reflection.name, "Foo");
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! The javascript bindings are compiled with the `wasm` feature.
+
+pub mod cache;
 mod err;
-mod parser;
-mod stack;
+pub mod parser;
+pub mod reflection;
+pub mod stack;
+#[cfg(feature = "wasm")]
 mod wasm;
 
-mod reflection;
 #[cfg(test)]
 pub mod testing;
 
-pub(crate) use err::SyntaxError;
-pub(crate) use stack::*;
+pub use err::{Diagnostic, Severity, SourceSpan, SyntaxError};
 use uuid::Uuid;
-#[cfg(feature = "simd")]
+#[cfg(all(feature = "simd", feature = "wasm"))]
 use uuid_simd::UuidExt;
 
 pub(crate) fn generate_uuid() -> Uuid {
@@ -21,6 +41,7 @@ pub(crate) fn generate_uuid() -> Uuid {
     return Uuid::new_v4();
 }
 
+#[cfg(feature = "wasm")]
 pub(crate) fn parse_uuid(text: &str) -> anyhow::Result<Uuid> {
     #[cfg(not(feature = "simd"))]
     let parsed = Uuid::parse_str(text)?;
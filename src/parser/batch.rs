@@ -0,0 +1,156 @@
+use crate::parser::{CodeParser, CompileOptions, CompileResult};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The outcome of the compilation of a single file of a batch.
+#[derive(Debug)]
+pub struct BatchFileResult {
+    pub filename: Option<String>,
+    pub result: anyhow::Result<CompileResult>,
+}
+
+/// Combined statistics of a batch compilation.
+#[derive(Clone, Debug, Default)]
+pub struct BatchStats {
+    pub files: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// The number of classes registered for reflection.
+    pub classes: usize,
+    pub input_bytes: usize,
+    pub output_bytes: usize,
+    pub threads: usize,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct BatchResult {
+    /// The per-file results, in the same order as the inputs.
+    pub files: Vec<BatchFileResult>,
+    pub stats: BatchStats,
+}
+
+fn compile_one(
+    source: &str,
+    filename: Option<&str>,
+    opts: &CompileOptions,
+) -> anyhow::Result<CompileResult> {
+    let program = source.parse_program(filename)?;
+    Ok(program.compile(opts.clone())?)
+}
+
+/// Compiles the given `(source, filename)` pairs in parallel, using as many
+/// threads as the available parallelism.
+/// Each compilation sets up its own swc globals, classes and source maps are
+/// registered into the shared registries as with a single compilation.
+pub fn compile_many(inputs: Vec<(String, Option<String>)>, opts: &CompileOptions) -> BatchResult {
+    let started = Instant::now();
+    let threads = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(inputs.len())
+        .max(1);
+
+    let next = &AtomicUsize::new(0);
+    let queue = &inputs;
+    let mut compiled = thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| {
+                scope.spawn(move || {
+                    let mut compiled = vec![];
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some((source, filename)) = queue.get(idx) else {
+                            break;
+                        };
+
+                        compiled.push((idx, compile_one(source, filename.as_deref(), opts)));
+                    }
+
+                    compiled
+                })
+            })
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect::<Vec<_>>()
+    });
+
+    compiled.sort_by_key(|(idx, _)| *idx);
+
+    let mut stats = BatchStats {
+        files: inputs.len(),
+        threads,
+        ..Default::default()
+    };
+
+    let files = inputs
+        .into_iter()
+        .zip(compiled)
+        .map(|((source, filename), (_, result))| {
+            stats.input_bytes += source.len();
+            match &result {
+                Ok(r) => {
+                    stats.succeeded += 1;
+                    stats.classes += r.class_ids.len();
+                    stats.output_bytes += r.code.len();
+                }
+                Err(_) => stats.failed += 1,
+            }
+
+            BatchFileResult { filename, result }
+        })
+        .collect();
+
+    stats.elapsed = started.elapsed();
+
+    BatchResult { files, stats }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compile_many;
+
+    #[test]
+    pub fn should_compile_files_in_parallel() {
+        let inputs = (0..16)
+            .map(|i| {
+                let source = if i == 7 {
+                    "export class {".to_string()
+                } else {
+                    format!(
+                        "export default class C{} {{}}\nexport class D{} {{}}\n",
+                        i, i
+                    )
+                };
+
+                (source, Some(format!("file{}.js", i)))
+            })
+            .collect::<Vec<_>>();
+
+        let result = compile_many(inputs, &Default::default());
+
+        assert_eq!(result.files.len(), 16);
+        assert_eq!(result.stats.files, 16);
+        assert_eq!(result.stats.succeeded, 15);
+        assert_eq!(result.stats.failed, 1);
+        assert_eq!(result.stats.classes, 30);
+        assert!(result.stats.threads >= 1);
+
+        for (i, file) in result.files.iter().enumerate() {
+            assert_eq!(
+                file.filename.as_deref(),
+                Some(format!("file{}.js", i).as_str())
+            );
+            if i == 7 {
+                assert!(file.result.is_err());
+            } else {
+                let compiled = file.result.as_ref().unwrap();
+                assert!(compiled.code.contains(&format!("class C{}", i)));
+            }
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use crate::parser::transformers::has_optional_attribute;
+use crate::SourceSpan;
+use serde::Serialize;
+use swc_common::source_map::Pos;
+use swc_common::{SourceMap, Span};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DependencyKind {
+    /// A static `import` declaration.
+    Import,
+    /// An `export ... from` declaration.
+    ReExport,
+    /// A `require()` call with a literal argument.
+    Require,
+    /// A dynamic `import()` with a literal argument.
+    DynamicImport,
+}
+
+/// A module the program depends on.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dependency {
+    pub specifier: String,
+    pub kind: DependencyKind,
+    /// Whether the import is marked with the `optional: true` attribute.
+    pub optional: bool,
+    /// The span of the specifier string literal.
+    pub span: SourceSpan,
+    /// The imported (or re-exported) names. `default` is used for default
+    /// imports and `*` for namespace imports and re-exports.
+    /// Empty for side-effect imports, `require()` and `import()` calls.
+    pub names: Vec<String>,
+}
+
+/// Collects the dependencies of the program, in order of appearance.
+pub(crate) fn collect_dependencies(program: &Program, source_map: &SourceMap) -> Vec<Dependency> {
+    let mut collector = DependencyCollector {
+        source_map,
+        dependencies: vec![],
+    };
+    program.visit_with(&mut collector);
+
+    collector.dependencies
+}
+
+/// Collects the specifiers of the modules the program depends on,
+/// in order of appearance and without duplicates.
+pub(crate) fn collect_specifiers(dependencies: &[Dependency]) -> Vec<String> {
+    let mut specifiers: Vec<String> = vec![];
+    for dependency in dependencies {
+        if !specifiers.contains(&dependency.specifier) {
+            specifiers.push(dependency.specifier.clone());
+        }
+    }
+
+    specifiers
+}
+
+struct DependencyCollector<'a> {
+    source_map: &'a SourceMap,
+    dependencies: Vec<Dependency>,
+}
+
+impl DependencyCollector<'_> {
+    fn add(&mut self, src: &Str, kind: DependencyKind, optional: bool, names: Vec<String>) {
+        self.dependencies.push(Dependency {
+            specifier: src.value.to_string(),
+            kind,
+            optional,
+            span: self.source_span(src.span),
+            names,
+        });
+    }
+
+    fn source_span(&self, span: Span) -> SourceSpan {
+        if span.is_dummy() {
+            return SourceSpan { start: 0, end: 0 };
+        }
+
+        let start = self.source_map.lookup_byte_offset(span.lo).pos.to_usize();
+        let end = self.source_map.lookup_byte_offset(span.hi).pos.to_usize();
+
+        SourceSpan { start, end }
+    }
+}
+
+/// Whether the options of a dynamic `import()` carry the `optional: true` attribute,
+/// ie: `import('./a', { with: { optional: true } })`.
+fn has_optional_import_option(options: &Expr) -> bool {
+    let Expr::Object(options) = options else {
+        return false;
+    };
+
+    options.props.iter().any(|p| {
+        p.as_prop().and_then(|p| p.as_key_value()).is_some_and(|kv| {
+            kv.key.as_ident().is_some_and(|i| i.sym == "with")
+                && kv.value.as_object().is_some_and(has_optional_attribute)
+        })
+    })
+}
+
+fn export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::Ident(i) => i.sym.to_string(),
+        ModuleExportName::Str(s) => s.value.to_string(),
+    }
+}
+
+impl Visit for DependencyCollector<'_> {
+    noop_visit_type!();
+
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        if n.type_only {
+            return;
+        }
+
+        let names = n
+            .specifiers
+            .iter()
+            .filter_map(|s| match s {
+                ImportSpecifier::Named(named) if !named.is_type_only => Some(
+                    named
+                        .imported
+                        .as_ref()
+                        .map(export_name)
+                        .unwrap_or_else(|| named.local.sym.to_string()),
+                ),
+                ImportSpecifier::Named(_) => None,
+                ImportSpecifier::Default(_) => Some("default".to_string()),
+                ImportSpecifier::Namespace(_) => Some("*".to_string()),
+            })
+            .collect();
+
+        let optional = n.with.as_deref().is_some_and(has_optional_attribute);
+        self.add(&n.src, DependencyKind::Import, optional, names);
+    }
+
+    fn visit_export_all(&mut self, n: &ExportAll) {
+        let optional = n.with.as_deref().is_some_and(has_optional_attribute);
+        self.add(
+            &n.src,
+            DependencyKind::ReExport,
+            optional,
+            vec!["*".to_string()],
+        );
+    }
+
+    fn visit_named_export(&mut self, n: &NamedExport) {
+        if n.type_only {
+            return;
+        }
+
+        let Some(src) = &n.src else {
+            return;
+        };
+
+        let names = n
+            .specifiers
+            .iter()
+            .filter_map(|s| match s {
+                ExportSpecifier::Named(named) if !named.is_type_only => {
+                    Some(export_name(&named.orig))
+                }
+                ExportSpecifier::Named(_) => None,
+                ExportSpecifier::Default(_) => Some("default".to_string()),
+                ExportSpecifier::Namespace(_) => Some("*".to_string()),
+            })
+            .collect();
+
+        let optional = n.with.as_deref().is_some_and(has_optional_attribute);
+        self.add(src, DependencyKind::ReExport, optional, names);
+    }
+
+    fn visit_ts_import_equals_decl(&mut self, n: &TsImportEqualsDecl) {
+        if n.is_type_only {
+            return;
+        }
+
+        if let TsModuleRef::TsExternalModuleRef(r) = &n.module_ref {
+            self.add(&r.expr, DependencyKind::Require, false, vec![]);
+        }
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        n.visit_children_with(self);
+
+        let kind = match &n.callee {
+            Callee::Import(_) => DependencyKind::DynamicImport,
+            Callee::Expr(e) if e.as_ident().is_some_and(|i| i.sym == "require") => {
+                DependencyKind::Require
+            }
+            _ => return,
+        };
+
+        let optional = kind == DependencyKind::DynamicImport
+            && n.args.get(1).is_some_and(|options| {
+                options.spread.is_none() && has_optional_import_option(&options.expr)
+            });
+
+        if let Some(ExprOrSpread { spread: None, expr }) = n.args.first() {
+            if let Expr::Lit(Lit::Str(s)) = expr.as_ref() {
+                self.add(s, kind, optional, vec![]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{CodeParser, DependencyKind};
+
+    #[test]
+    pub fn should_list_program_dependencies() -> anyhow::Result<()> {
+        let code = r#"import Redis, { Cluster as RedisCluster } from 'ioredis' with { optional: true };
+import * as path from 'path';
+import './side-effect';
+export { a, b as c } from './ab';
+export * from './all';
+export { d } from './optional-d' with { optional: true };
+export * from './optional-all' with { optional: 'true' };
+const fs = require('fs');
+const lazy = () => import('./lazy');
+const optionalLazy = () => import('./optional-lazy', { with: { optional: true } });
+require(dynamicName);
+"#;
+
+        let dependencies = code.parse_program(Some("a.js"))?.dependencies();
+        let summary = dependencies
+            .iter()
+            .map(|d| (d.specifier.as_str(), d.kind, d.optional, d.names.join(",")))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "ioredis",
+                    DependencyKind::Import,
+                    true,
+                    "default,Cluster".to_string()
+                ),
+                ("path", DependencyKind::Import, false, "*".to_string()),
+                (
+                    "./side-effect",
+                    DependencyKind::Import,
+                    false,
+                    "".to_string()
+                ),
+                ("./ab", DependencyKind::ReExport, false, "a,b".to_string()),
+                ("./all", DependencyKind::ReExport, false, "*".to_string()),
+                (
+                    "./optional-d",
+                    DependencyKind::ReExport,
+                    true,
+                    "d".to_string()
+                ),
+                (
+                    "./optional-all",
+                    DependencyKind::ReExport,
+                    true,
+                    "*".to_string()
+                ),
+                ("fs", DependencyKind::Require, false, "".to_string()),
+                (
+                    "./lazy",
+                    DependencyKind::DynamicImport,
+                    false,
+                    "".to_string()
+                ),
+                (
+                    "./optional-lazy",
+                    DependencyKind::DynamicImport,
+                    true,
+                    "".to_string()
+                ),
+            ]
+        );
+
+        let span = &dependencies[0].span;
+        assert_eq!(&code[span.start..span.end], "'ioredis'");
+
+        let span = &dependencies[7].span;
+        assert_eq!(&code[span.start..span.end], "'fs'");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use swc_common::comments::Comments;
+use swc_common::sync::Lrc;
+use swc_common::{Mark, SourceMap};
+use swc_ecma_ast::Program;
+use swc_ecma_minifier::optimize;
+use swc_ecma_minifier::option::terser::TerserCompressorOptions;
+use swc_ecma_minifier::option::{CompressOptions, ExtraOptions, MangleOptions, MinifyOptions};
+use swc_ecma_transforms_base::fixer::fixer;
+use swc_ecma_visit::FoldWith;
+
+/// Compresses a program whose classes have not been lowered yet.
+///
+/// The compressor drops the class expressions whose value is unused, keeping their
+/// static initializers only: run on the output of the decorators transform, it
+/// removes the decorated classes and evaluates their initializers against the module
+/// `this`. Hence it must run before the decorators and the class passes.
+/// Unused names are kept (`unused: false`), as `var f = function f() {}` would
+/// otherwise lose the function names given by the anonymous expressions pass.
+pub(crate) fn compress(
+    program: Program,
+    source_map: Lrc<SourceMap>,
+    comments: Option<&dyn Comments>,
+    unresolved_mark: Mark,
+    top_level_mark: Mark,
+) -> Program {
+    let options = MinifyOptions {
+        compress: Some(CompressOptions {
+            keep_classnames: true,
+            keep_fnames: true,
+            unused: false,
+            ..TerserCompressorOptions::default().into_config(source_map.clone())
+        }),
+        ..Default::default()
+    };
+
+    optimize(
+        program,
+        source_map,
+        comments,
+        None,
+        &options,
+        &ExtraOptions {
+            unresolved_mark,
+            top_level_mark,
+        },
+    )
+}
+
+/// Mangles the local names of an already transformed program.
+///
+/// Class and function names are kept: reflection looks classes up by name and
+/// stack traces rely on the names given to anonymous class and function expressions.
+/// `__jymfony_reflect` and `__jymfony_metadata` are globals, hence neither their names
+/// nor their arguments are mangled.
+pub(crate) fn mangle(
+    program: Program,
+    source_map: Lrc<SourceMap>,
+    comments: Option<&dyn Comments>,
+    unresolved_mark: Mark,
+    top_level_mark: Mark,
+) -> Program {
+    let options = MinifyOptions {
+        mangle: Some(MangleOptions {
+            keep_class_names: true,
+            keep_fn_names: true,
+            reserved: vec!["__jymfony_reflect".into(), "__jymfony_metadata".into()],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let program = optimize(
+        program,
+        source_map,
+        comments,
+        None,
+        &options,
+        &ExtraOptions {
+            unresolved_mark,
+            top_level_mark,
+        },
+    );
+
+    program.fold_with(&mut fixer(comments))
+}
@@ -1,8 +1,15 @@
+//! Parsing and compilation of javascript and typescript sources.
+
 use crate::SyntaxError;
 use anyhow::{Error, Result};
-pub use program::CompileOptions;
-use program::Program;
-use std::path::PathBuf;
+pub use batch::{compile_many, BatchFileResult, BatchResult, BatchStats};
+pub use dependencies::{Dependency, DependencyKind};
+pub use program::{
+    CompileOptions, CompileResult, JsxRuntime, Program, ReflectionMode, SourceMapMode,
+    SourceMapOptions,
+};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use swc_common::comments::SingleThreadedComments;
 use swc_common::input::StringInput;
@@ -13,11 +20,16 @@ use swc_ecma_parser::lexer::Lexer;
 use swc_ecma_parser::token::{IdentLike, Token, Word};
 use swc_ecma_parser::{EsConfig, Parser, Syntax, TsConfig};
 
+mod batch;
+mod dependencies;
+mod minify;
 mod program;
 mod sourcemap;
 mod transformers;
 mod util;
 
+pub(crate) use transformers::ANONYMOUS_PREFIX;
+
 const ES_VERSION: EsVersion = EsVersion::EsNext;
 const ES_CONFIG: EsConfig = EsConfig {
     jsx: false,
@@ -32,7 +44,32 @@ const ES_CONFIG: EsConfig = EsConfig {
     explicit_resource_management: true,
 };
 
+/// Whether a source file has to be parsed as an ES module or as a script (CommonJS).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum ModuleKind {
+    Module,
+    Script,
+}
+
+impl ModuleKind {
+    /// Infers the module kind from the filename extension.
+    /// Returns `None` if the extension is ambiguous (ie: `.js` or `.ts`).
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        match Path::new(filename).extension().and_then(|e| e.to_str()) {
+            Some("mjs" | "mts") => Some(Self::Module),
+            Some("cjs" | "cts") => Some(Self::Script),
+            _ => None,
+        }
+    }
+}
+
+/// Parses source code into a `Program`.
+/// The file extension of the given filename selects the syntax: typescript
+/// for `.ts`, `.mts` and `.cts` files, JSX for `.jsx` and `.tsx` files.
 pub trait CodeParser {
+    /// Parses the program, inferring the module kind from the filename
+    /// extension, or from the content if the extension is ambiguous.
+    /// The inferred kind can be overridden with `CompileOptions::module_kind`.
     fn parse_program(self, filename: Option<&str>) -> Result<Program>;
 }
 
@@ -53,17 +90,25 @@ impl CodeParser for &str {
         );
 
         let comments = SingleThreadedComments::default();
-        let is_typescript = filename.is_some_and(|f| f.ends_with(".ts"));
+        let extension = filename
+            .and_then(|f| Path::new(f).extension())
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        let is_typescript = matches!(extension, "ts" | "tsx" | "mts" | "cts");
+        let is_jsx = matches!(extension, "jsx" | "tsx");
         let syntax = if is_typescript {
             Syntax::Typescript(TsConfig {
-                tsx: false,
+                tsx: is_jsx,
                 decorators: true,
                 dts: false,
                 no_early_errors: false,
                 disallow_ambiguous_jsx_like: false,
             })
         } else {
-            Syntax::Es(ES_CONFIG)
+            Syntax::Es(EsConfig {
+                jsx: is_jsx,
+                ..ES_CONFIG
+            })
         };
 
         let lexer = Lexer::new(
@@ -73,28 +118,39 @@ impl CodeParser for &str {
             Some(&comments),
         );
 
+        let module_kind = filename.and_then(ModuleKind::from_filename);
         let mut parser = Parser::new_from(lexer);
-        let parse_result = parser.parse_program();
+        let parse_result = match module_kind {
+            Some(ModuleKind::Module) => parser.parse_module().map(swc_ecma_ast::Program::Module),
+            Some(ModuleKind::Script) => parser.parse_script().map(swc_ecma_ast::Program::Script),
+            None => parser.parse_program(),
+        };
         let orig_srcmap = sourcemap::get_orig_src_map(&source_file).unwrap_or_default();
 
-        if let Ok(program) = parse_result {
-            let errors = parser.take_errors();
-            if !errors.is_empty() {
-                let e = errors.first().unwrap();
-                Err(SyntaxError::from_parser_error(e, &source_file).into())
-            } else {
-                Ok(Program {
-                    source_map,
-                    orig_srcmap,
-                    filename: filename.map(|f| f.to_string()),
-                    program,
-                    comments: Rc::new(comments),
-                    is_typescript,
-                })
+        match parse_result {
+            Ok(program) => {
+                let errors = parser.take_errors();
+                if !errors.is_empty() {
+                    Err(SyntaxError::from_parser_errors(&errors, &source_file, filename).into())
+                } else {
+                    Ok(Program {
+                        source_map,
+                        orig_srcmap,
+                        filename: filename.map(|f| f.to_string()),
+                        program,
+                        comments: Rc::new(comments),
+                        is_typescript,
+                        is_jsx,
+                    })
+                }
+            }
+            Err(e) => {
+                let errors = std::iter::once(e)
+                    .chain(parser.take_errors())
+                    .collect::<Vec<_>>();
+
+                Err(SyntaxError::from_parser_errors(&errors, &source_file, filename).into())
             }
-        } else {
-            let e = parse_result.unwrap_err();
-            Err(SyntaxError::from_parser_error(&e, &source_file).into())
         }
     }
 }
@@ -160,6 +216,8 @@ mod tests {
     use crate::parser::transformers::decorator_2022_03;
     use crate::testing::exec_tr;
     use crate::testing::uuid::reset_test_uuid;
+    use crate::Severity;
+    use crate::SyntaxError;
     use std::path::PathBuf;
     use swc_common::{chain, Mark};
     use swc_ecma_parser::{EsConfig, Syntax};
@@ -237,7 +295,8 @@ export class y {
         let result = code.parse_program(None).expect("failed to parse_program");
         let compiled = result
             .compile(Default::default())
-            .expect("failed to compile");
+            .expect("failed to compile")
+            .code;
 
         assert_eq!(
             compiled,
@@ -259,10 +318,10 @@ _export(exports, {
         return _y;
     }
 });
-var _initClass, __anonymous_xΞ1, _dec, __jymfony_JObject, _dec1, _initProto, _dec2, _initClass1, __jymfony_JObject1, _dec3, _initProto1, _dec4, _initClass2, __jymfony_JObject2, _dec5, _initProto2;
+var _initClass, _p, _dec, __jymfony_JObject, _dec1, _initProto, _dec2, _initClass1, __jymfony_JObject1, _dec3, _initProto1, _dec4, _initClass2, __jymfony_JObject2, _dec5, _initProto2;
 _dec = __jymfony_reflect("00000000-0000-0000-0000-000000000000", void 0), _dec1 = __jymfony_reflect("00000000-0000-0000-0000-000000000000", 0);
-const p = (class _anonymous_xΞ1 extends (__jymfony_JObject = __jymfony.JObject) {
-    static #_ = { e: [_initProto], c: [__anonymous_xΞ1, _initClass] } = _apply_decs_2203_r(this, [
+const p = (class p extends (__jymfony_JObject = __jymfony.JObject) {
+    static #_ = { e: [_initProto], c: [_p, _initClass] } = _apply_decs_2203_r(this, [
         [
             _dec1,
             2,
@@ -276,10 +335,10 @@ const p = (class _anonymous_xΞ1 extends (__jymfony_JObject = __jymfony.JObject)
         _initProto(this);
     }
     t() {
-        return __anonymous_xΞ1.x;
+        return _p.x;
     }
     static #_2 = _initClass();
-}, __anonymous_xΞ1);
+}, _p);
 let _x;
 _dec2 = __jymfony_reflect("00000000-0000-0000-0000-000000000001", void 0), _dec3 = __jymfony_reflect("00000000-0000-0000-0000-000000000001", 0);
 class x extends (__jymfony_JObject1 = __jymfony.JObject) {
@@ -318,10 +377,10 @@ class y extends (__jymfony_JObject2 = __jymfony.JObject) {
         _initProto2(this);
     }
     f() {
-        var _initClass, __anonymous_xΞ2, _dec, __jymfony_JObject, _dec1, _initProto;
+        var _initClass, __anonymous_xΞ1, _dec, __jymfony_JObject, _dec1, _initProto;
         _dec = __jymfony_reflect("00000000-0000-0000-0000-000000000003", void 0), _dec1 = __jymfony_reflect("00000000-0000-0000-0000-000000000003", 0);
-        return _construct_jobject((class _anonymous_xΞ2 extends (__jymfony_JObject = __jymfony.JObject) {
-            static #_ = { e: [_initProto], c: [__anonymous_xΞ2, _initClass] } = _apply_decs_2203_r(this, [
+        return _construct_jobject((class _anonymous_xΞ1 extends (__jymfony_JObject = __jymfony.JObject) {
+            static #_ = { e: [_initProto], c: [__anonymous_xΞ1, _initClass] } = _apply_decs_2203_r(this, [
                 [
                     _dec1,
                     2,
@@ -335,10 +394,10 @@ class y extends (__jymfony_JObject2 = __jymfony.JObject) {
                 _initProto(this);
             }
             c() {
-                return __anonymous_xΞ2;
+                return __anonymous_xΞ1;
             }
             static #_2 = _initClass();
-        }, __anonymous_xΞ2));
+        }, __anonymous_xΞ1));
     }
     static #_2 = _initClass2();
 }
@@ -359,6 +418,103 @@ new class ext impl test {[]}
             error.to_string(),
             "SyntaxError: Expected '{', got 'impl' on line 2, column 15"
         );
+
+        let error = error.downcast_ref::<SyntaxError>().unwrap();
+        assert_eq!(
+            error.code_frame(),
+            Some("  1 |\n> 2 | new class ext impl test {[]}\n    |               ^^^^")
+        );
+    }
+
+    #[test]
+    pub fn parse_error_should_report_utf16_columns() {
+        let code = "const s = '😀'; new class ext impl test {[]}";
+        let error = code.parse_program(Some("a.js")).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "SyntaxError: Expected '{', got 'impl' on line 1, column 31"
+        );
+    }
+
+    #[test]
+    pub fn parse_error_should_carry_diagnostics() {
+        let code = r#"
+export const a = 010;
+export const b = 'ok';
+export const c = 011;
+"#;
+        let result = code.parse_program(Some("a.js"));
+        let error = result.unwrap_err();
+        let error = error
+            .downcast_ref::<SyntaxError>()
+            .expect("should be a syntax error");
+
+        let diagnostics = error.diagnostics();
+        assert_eq!(diagnostics.len(), 2);
+
+        assert_eq!(diagnostics[0].filename.as_deref(), Some("a.js"));
+        assert_eq!(diagnostics[0].line, 2);
+        assert_eq!(diagnostics[0].column, 18);
+        assert_eq!(diagnostics[0].span.start, 18);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+
+        assert_eq!(diagnostics[1].line, 4);
+        assert_eq!(diagnostics[1].column, 18);
+        assert_eq!(diagnostics[1].kind, diagnostics[0].kind);
+    }
+
+    #[test]
+    pub fn parse_error_should_describe_the_first_error_in_source_order() {
+        // The legacy octal literal is a recoverable error, reported after the fatal one.
+        let code = "export const a = 010;\nexport const b = ;\n";
+        let error = code.parse_program(Some("a.js")).unwrap_err();
+        let error = error
+            .downcast_ref::<SyntaxError>()
+            .expect("should be a syntax error");
+
+        let diagnostics = error.diagnostics();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].line, 1);
+        assert_eq!(diagnostics[1].line, 2);
+
+        assert_eq!(error.to_string(), diagnostics[0].to_string());
+        assert_eq!(error.code_frame(), diagnostics[0].code_frame.as_deref());
+    }
+
+    #[test]
+    pub fn should_infer_module_kind_from_extension() -> anyhow::Result<()> {
+        let code = "const a = 1;";
+
+        assert!(code.parse_program(Some("a.mjs"))?.program.is_module());
+        assert!(code.parse_program(Some("a.mts"))?.program.is_module());
+        assert!(code.parse_program(Some("a.cjs"))?.program.is_script());
+        assert!(code.parse_program(Some("a.cts"))?.program.is_script());
+
+        assert!("import a from 'a';".parse_program(Some("a.cjs")).is_err());
+        assert!("export default 1;".parse_program(Some("a.mjs")).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_parse_cts_and_mts_as_typescript() -> anyhow::Result<()> {
+        let code = r#"
+const path: string = require('path');
+module.exports = function (p: string): string { return path.resolve(p); };
+"#;
+
+        let program = code.parse_program(Some("a.cts"))?;
+        assert!(program.is_typescript);
+
+        let compiled = program.compile(Default::default())?.code;
+        assert!(!compiled.contains(": string"));
+
+        let program = "export const a: number = 1;".parse_program(Some("a.mts"))?;
+        assert!(program.is_typescript);
+        assert!(program.program.is_module());
+
+        Ok(())
     }
 
     #[test]
@@ -1,18 +1,30 @@
+use crate::parser::dependencies::{collect_dependencies, collect_specifiers, Dependency};
+use crate::parser::minify::{compress, mangle};
+use crate::parser::sourcemap::SourceMapConfig;
 use crate::parser::transformers::{
-    anonymous_expr, class_jobject, class_reflection_decorators, decorator_2022_03,
-    lazy_object_construction, optional_import, remove_assert_calls, resolve_self_identifiers,
-    static_blocks, wrap_in_function,
+    anonymous_expr, async_generators, class_jobject, class_reflection_decorators,
+    decorator_2022_03, design_metadata, implements_mixins, lazy_object_construction,
+    optional_import, remove_assert_calls, resolve_self_identifiers, static_blocks,
+    wrap_in_function, CollectedClass,
 };
-use crate::stack::register_source_map;
+use crate::parser::ModuleKind;
+use crate::reflection::register_file;
+use crate::Diagnostic;
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
+use serde::Serialize;
 use sourcemap::SourceMap;
+use std::cell::RefCell;
 use std::fmt::{Debug, Formatter};
+use std::io::ErrorKind;
+use std::path::Path;
 use std::rc::Rc;
+use std::sync::Arc;
 use swc_cached::regex::CachedRegex;
 use swc_common::comments::SingleThreadedComments;
 use swc_common::sync::Lrc;
 use swc_common::{chain, BytePos, LineCol, Mark, GLOBALS};
+use swc_ecma_ast::{EsVersion, Module, ModuleItem, Script};
 use swc_ecma_codegen::text_writer::JsWriter;
 use swc_ecma_codegen::Emitter;
 use swc_ecma_transforms_base::feature::FeatureFlag;
@@ -20,20 +32,161 @@ use swc_ecma_transforms_base::fixer::fixer;
 use swc_ecma_transforms_base::helpers::{inject_helpers, Helpers, HELPERS};
 use swc_ecma_transforms_base::hygiene::{hygiene_with_config, Config as HygieneConfig};
 use swc_ecma_transforms_base::resolver;
+use swc_ecma_transforms_compat::es2018::object_rest_spread;
+use swc_ecma_transforms_compat::es2019::optional_catch_binding;
 use swc_ecma_transforms_compat::es2020::{nullish_coalescing, optional_chaining};
+use swc_ecma_transforms_compat::es2021::logical_assignments;
+use swc_ecma_transforms_compat::es2022::{class_properties, private_in_object};
 use swc_ecma_transforms_module::common_js;
 use swc_ecma_transforms_module::util::{ImportInterop, Lazy, LazyObjectConfig};
+use swc_ecma_transforms_react::{react, Options as ReactOptions, Runtime};
 use swc_ecma_transforms_typescript::strip;
 use swc_ecma_visit::{Fold, FoldWith};
+use uuid::Uuid;
+
+/// The runtime used to compile JSX elements.
+#[derive(Clone, Debug, Serialize)]
+pub enum JsxRuntime {
+    /// Compiles JSX into calls to `pragma` (defaults to `React.createElement`)
+    /// and fragments into `pragma_frag` (defaults to `React.Fragment`).
+    Classic {
+        pragma: Option<String>,
+        pragma_frag: Option<String>,
+    },
+    /// Compiles JSX into calls to the functions exported by `<import_source>/jsx-runtime`
+    /// (`import_source` defaults to `react`).
+    Automatic { import_source: Option<String> },
+}
+
+impl Default for JsxRuntime {
+    fn default() -> Self {
+        Self::Classic {
+            pragma: None,
+            pragma_frag: None,
+        }
+    }
+}
+
+impl From<&JsxRuntime> for ReactOptions {
+    fn from(value: &JsxRuntime) -> Self {
+        match value {
+            JsxRuntime::Classic {
+                pragma,
+                pragma_frag,
+            } => ReactOptions {
+                runtime: Some(Runtime::Classic),
+                pragma: pragma.clone(),
+                pragma_frag: pragma_frag.clone(),
+                ..Default::default()
+            },
+            JsxRuntime::Automatic { import_source } => ReactOptions {
+                runtime: Some(Runtime::Automatic),
+                import_source: import_source.clone(),
+                ..Default::default()
+            },
+        }
+    }
+}
 
-#[derive(Default)]
+/// How the source map is emitted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub enum SourceMapMode {
+    /// Appends the source map as a base64 data url.
+    /// The source map is generated only if a filename is given.
+    #[default]
+    Inline,
+    /// Returns the source map and appends a `sourceMappingURL=<file>.map` comment.
+    External,
+    /// Returns the source map without referencing it from the compiled code.
+    Hidden,
+    /// Does not generate any source map.
+    None,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SourceMapOptions {
+    pub mode: SourceMapMode,
+    pub source_root: Option<String>,
+    /// Whether to embed the original sources into the source map.
+    pub sources_content: bool,
+    /// The name of the compiled file, referenced by the source map.
+    /// Defaults to the file name of the compiled program.
+    pub file: Option<String>,
+    /// The path of the compiled program recorded into the map `sources`, ie: relative
+    /// to the location of the emitted map. Defaults to the program filename.
+    /// The source maps registered for stack traces keep the filename.
+    pub source_path: Option<String>,
+}
+
+/// Where the reflection metadata of the compiled classes is stored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub enum ReflectionMode {
+    /// Registers the metadata into the in-process registry,
+    /// looked up at runtime through `getInternalReflectionData`.
+    #[default]
+    Registry,
+    /// Embeds the metadata as a literal into the compiled code, which is then
+    /// self-contained. Classes are not registered into the registry.
+    Inline,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct CompileOptions {
+    /// Keeps the `assert` calls, removed otherwise.
     pub debug: bool,
+    /// The namespace the compiled classes are registered into.
+    /// A `@memberOf` or `@namespace` tag in the docblock of a class overrides it.
     pub namespace: Option<String>,
+    /// Wraps the compiled code into a function.
     pub as_function: bool,
+    /// Keeps the ES module syntax instead of compiling to CommonJS.
     pub as_module: bool,
+    pub jsx: JsxRuntime,
+    /// Overrides the module kind inferred at parse time: scripts are promoted to
+    /// modules, modules are compiled as scripts if they have no import or export.
+    pub module_kind: Option<ModuleKind>,
+    pub source_map: SourceMapOptions,
+    /// The ECMAScript version the compiled code must run on (ES2017 or later,
+    /// older targets are rejected).
+    /// Syntax not supported by the target is downleveled; when unset only
+    /// nullish coalescing, optional chaining and static blocks are rewritten.
+    pub target: Option<EsVersion>,
+    /// Compresses and mangles the compiled code.
+    pub minify: bool,
+    /// Derives the class ids from the filename, namespace, class name and position
+    /// instead of generating random ones, making the compiled output reproducible.
+    pub deterministic_ids: bool,
+    pub reflection: ReflectionMode,
+    /// Emits the design-time types of decorated TypeScript classes and members
+    /// (`design:type`, `design:paramtypes` and `design:returntype`) into the
+    /// decorator metadata, as TypeScript's `emitDecoratorMetadata` does.
+    /// The imports referenced by these types are not elided.
+    pub emit_decorator_metadata: bool,
+    /// Applies the interfaces listed by the `implements` clause of TypeScript
+    /// classes as Jymfony mixins (`extends mix(Base, ...interfaces)`), so that
+    /// `instanceof` checks against them hold at runtime.
+    /// Only the interfaces bound to a value are applied: the ones declared with
+    /// `getInterface` and the imported ones (use `import type` for TypeScript interfaces).
+    /// The compiled code calls the global `mix` function, which must be defined at
+    /// runtime (ie: by `@jymfony/util`).
+    pub implements_mixins: bool,
+}
+
+/// The output of a compilation.
+#[derive(Clone, Debug)]
+pub struct CompileResult {
+    /// The compiled code.
+    pub code: String,
+    /// The source map of the compiled code, serialized as JSON.
+    pub source_map: Option<String>,
+    /// The ids of the classes registered for reflection.
+    pub class_ids: Vec<Uuid>,
+    /// The specifiers of the imported and required modules.
+    pub dependencies: Vec<String>,
+    pub warnings: Vec<Diagnostic>,
 }
 
+/// A parsed program, ready to be compiled.
 pub struct Program {
     pub(crate) source_map: Lrc<swc_common::SourceMap>,
     pub(crate) orig_srcmap: Option<SourceMap>,
@@ -41,6 +194,7 @@ pub struct Program {
     pub(crate) program: swc_ecma_ast::Program,
     pub(crate) comments: Rc<SingleThreadedComments>,
     pub(crate) is_typescript: bool,
+    pub(crate) is_jsx: bool,
 }
 
 impl Debug for Program {
@@ -50,12 +204,70 @@ impl Debug for Program {
             .field("program", &self.program)
             .field("comments", &self.comments)
             .field("is_typescript", &self.is_typescript)
+            .field("is_jsx", &self.is_jsx)
             .finish_non_exhaustive()
     }
 }
 
 impl Program {
-    pub fn compile(self, opts: CompileOptions) -> std::io::Result<String> {
+    /// Lists the static imports, re-exports, `require()` calls and dynamic
+    /// `import()`s of the program, in order of appearance.
+    pub fn dependencies(&self) -> Vec<Dependency> {
+        collect_dependencies(&self.program, &self.source_map)
+    }
+
+    /// Converts the parsed program to the requested module kind.
+    fn into_module_kind(
+        program: swc_ecma_ast::Program,
+        module_kind: Option<ModuleKind>,
+    ) -> std::io::Result<swc_ecma_ast::Program> {
+        match (module_kind, program) {
+            (Some(ModuleKind::Module), swc_ecma_ast::Program::Script(script)) => {
+                Ok(swc_ecma_ast::Program::Module(Module {
+                    span: script.span,
+                    body: script.body.into_iter().map(ModuleItem::Stmt).collect(),
+                    shebang: script.shebang,
+                }))
+            }
+            (Some(ModuleKind::Script), swc_ecma_ast::Program::Module(module)) => {
+                let body = module
+                    .body
+                    .into_iter()
+                    .map(|item| match item {
+                        ModuleItem::Stmt(stmt) => Ok(stmt),
+                        ModuleItem::ModuleDecl(_) => Err(std::io::Error::new(
+                            ErrorKind::InvalidInput,
+                            "import and export declarations cannot be compiled as a script",
+                        )),
+                    })
+                    .collect::<std::io::Result<Vec<_>>>()?;
+
+                Ok(swc_ecma_ast::Program::Script(Script {
+                    span: module.span,
+                    body,
+                    shebang: module.shebang,
+                }))
+            }
+            (_, program) => Ok(program),
+        }
+    }
+
+    /// Compiles the program, registering its classes for reflection and,
+    /// if the program has a filename, its source map for stack trace remapping.
+    /// The classes and the source map of a previous compilation of the same file are replaced.
+    pub fn compile(self, opts: CompileOptions) -> std::io::Result<CompileResult> {
+        if opts.target.is_some_and(|t| t < EsVersion::Es2017) {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                "unsupported target: the compiled code requires ES2017 or later",
+            ));
+        }
+
+        let program = Self::into_module_kind(self.program, opts.module_kind)?;
+        let dependencies = collect_specifiers(&collect_dependencies(&program, &self.source_map));
+        let classes: Rc<RefCell<Vec<CollectedClass>>> = Default::default();
+        let warnings: Rc<RefCell<Vec<Diagnostic>>> = Default::default();
+
         GLOBALS.set(&Default::default(), || {
             let helpers = Helpers::new(false);
             HELPERS.set(&helpers, || {
@@ -63,6 +275,9 @@ impl Program {
                 let top_level_mark = Mark::new();
                 let static_blocks_mark = Mark::new();
                 let available_set = FeatureFlag::all();
+                let lowers = |version: EsVersion| opts.target.is_some_and(|t| t < version);
+                let lower_es2020 = opts.target.is_none() || lowers(EsVersion::Es2020);
+                let lower_static_blocks = opts.target.is_none() || lowers(EsVersion::Es2022);
 
                 let common_js_config = common_js::Config {
                     import_interop: Some(ImportInterop::Swc),
@@ -72,25 +287,114 @@ impl Program {
                     ..Default::default()
                 };
 
-                let mut transformers: Box<dyn Fold> = Box::new(chain!(
-                    resolver(unresolved_mark, top_level_mark, self.is_typescript),
-                    anonymous_expr(),
+                let mut transformers: Box<dyn Fold> = Box::new(resolver(
+                    unresolved_mark,
+                    top_level_mark,
+                    self.is_typescript,
+                ));
+
+                if self.is_jsx {
+                    transformers = Box::new(chain!(
+                        transformers,
+                        react(
+                            self.source_map.clone(),
+                            Some(&self.comments),
+                            ReactOptions::from(&opts.jsx),
+                            top_level_mark,
+                            unresolved_mark,
+                        ),
+                    ));
+                }
+
+                transformers =
+                    Box::new(chain!(transformers, anonymous_expr(self.filename.as_deref())));
+
+                // Runs before the reflection decorators are added, as only the
+                // classes and members decorated in the source are concerned.
+                if self.is_typescript && opts.emit_decorator_metadata {
+                    transformers = Box::new(chain!(transformers, design_metadata()));
+                }
+
+                transformers = Box::new(chain!(
+                    transformers,
                     class_reflection_decorators(
                         self.filename.as_deref(),
-                        opts.namespace.as_deref(),
-                        self.comments.clone()
+                        &opts,
+                        self.comments.clone(),
+                        self.source_map.clone(),
+                        classes.clone(),
+                        warnings.clone(),
                     ),
+                ));
+
+                // Must run before strip removes the `implements` clauses.
+                if self.is_typescript && opts.implements_mixins {
+                    transformers = Box::new(chain!(transformers, implements_mixins()));
+                }
+
+                transformers = Box::new(chain!(
+                    transformers,
                     strip(top_level_mark),
                     optional_import(unresolved_mark),
-                    nullish_coalescing(Default::default()),
-                    optional_chaining(Default::default(), unresolved_mark),
+                ));
+
+                if lower_es2020 {
+                    transformers = Box::new(chain!(
+                        transformers,
+                        nullish_coalescing(Default::default()),
+                        optional_chaining(Default::default(), unresolved_mark),
+                    ));
+                }
+
+                let mut program = program.fold_with(transformers.as_mut());
+                if opts.minify {
+                    program = compress(
+                        program,
+                        self.source_map.clone(),
+                        Some(&self.comments),
+                        unresolved_mark,
+                        top_level_mark,
+                    );
+                }
+
+                let mut transformers: Box<dyn Fold> = Box::new(chain!(
                     resolve_self_identifiers(unresolved_mark),
                     class_jobject(),
                     decorator_2022_03(),
                     lazy_object_construction(),
-                    static_blocks(static_blocks_mark),
                 ));
 
+                if lower_static_blocks {
+                    transformers = Box::new(chain!(transformers, static_blocks(static_blocks_mark)));
+                }
+
+                // Class fields and private members are emitted by the decorators
+                // transform, hence the class passes must run after it.
+                if lowers(EsVersion::Es2022) {
+                    transformers = Box::new(chain!(
+                        transformers,
+                        class_properties(Some(&self.comments), Default::default(), unresolved_mark),
+                        private_in_object(),
+                    ));
+                }
+
+                if lowers(EsVersion::Es2021) {
+                    transformers = Box::new(chain!(transformers, logical_assignments()));
+                }
+
+                if lowers(EsVersion::Es2019) {
+                    transformers = Box::new(chain!(transformers, optional_catch_binding()));
+                }
+
+                // Async functions are supported by every target, async iteration is not.
+                if lowers(EsVersion::Es2018) {
+                    transformers = Box::new(chain!(
+                        transformers,
+                        object_rest_spread(Default::default()),
+                        async_generators(Some(&self.comments), unresolved_mark),
+                    ));
+                }
+
                 if !opts.as_module {
                     transformers = Box::new(chain!(
                         transformers,
@@ -126,15 +430,29 @@ impl Program {
                     inject_helpers(top_level_mark),
                 ));
 
-                let program = self.program.fold_with(transformers.as_mut());
+                let mut program = program.fold_with(transformers.as_mut());
+                if opts.minify {
+                    program = mangle(
+                        program,
+                        self.source_map.clone(),
+                        Some(&self.comments),
+                        unresolved_mark,
+                        top_level_mark,
+                    );
+                }
+
                 let mut buf = vec![];
                 let mut sm: Vec<(BytePos, LineCol)> = vec![];
 
                 {
                     let mut emitter = Emitter {
-                        cfg: Default::default(),
+                        cfg: swc_ecma_codegen::Config::default().with_minify(opts.minify),
                         cm: self.source_map.clone(),
-                        comments: Some(&self.comments),
+                        comments: if opts.minify {
+                            None
+                        } else {
+                            Some(&self.comments)
+                        },
                         wr: JsWriter::new(Default::default(), "\n", &mut buf, Some(&mut sm)),
                     };
 
@@ -142,22 +460,87 @@ impl Program {
                 };
 
                 let mut src = String::from_utf8(buf).expect("non-utf8?");
-                if let Some(f) = self.filename.as_deref() {
-                    let srcmap = self
-                        .source_map
-                        .build_source_map_from(&sm, self.orig_srcmap.as_ref());
+                let mut source_map = None;
+                let mut registered_map = None;
+                let sm_opts = &opts.source_map;
+                let should_emit = match sm_opts.mode {
+                    SourceMapMode::Inline => self.filename.is_some(),
+                    SourceMapMode::External | SourceMapMode::Hidden => true,
+                    SourceMapMode::None => false,
+                };
 
-                    register_source_map(f.to_string(), srcmap.clone());
+                if should_emit {
+                    let mut srcmap = self.source_map.build_source_map_with_config(
+                        &sm,
+                        self.orig_srcmap.as_ref(),
+                        SourceMapConfig {
+                            sources_content: sm_opts.sources_content,
+                        },
+                    );
+
+                    let file = sm_opts.file.clone().or_else(|| {
+                        self.filename
+                            .as_deref()
+                            .and_then(|f| Path::new(f).file_name())
+                            .map(|f| f.to_string_lossy().to_string())
+                    });
+
+                    srcmap.set_file(file.as_deref());
+                    srcmap.set_source_root(sm_opts.source_root.as_deref());
+
+                    if let Some(filename) = self.filename.as_deref() {
+                        registered_map = Some(srcmap.clone());
+                        if let Some(source_path) = sm_opts.source_path.as_deref() {
+                            for idx in 0..srcmap.get_source_count() {
+                                if srcmap.get_source(idx) == Some(filename) {
+                                    srcmap.set_source(idx, source_path);
+                                }
+                            }
+                        }
+                    }
 
                     let mut buf = vec![];
                     srcmap.to_writer(&mut buf).ok();
 
-                    let res = BASE64_STANDARD.encode(buf);
-                    src += "\n\n//# sourceMappingURL=data:application/json;charset=utf-8;base64,";
-                    src += &res;
+                    match sm_opts.mode {
+                        SourceMapMode::Inline => {
+                            let res = BASE64_STANDARD.encode(&buf);
+                            src += "\n\n//# sourceMappingURL=data:application/json;charset=utf-8;base64,";
+                            src += &res;
+                        }
+                        SourceMapMode::External => {
+                            let Some(file) = file else {
+                                return Err(std::io::Error::new(
+                                    ErrorKind::InvalidInput,
+                                    "external source maps require a filename or a mapped file name",
+                                ));
+                            };
+
+                            src += "\n\n//# sourceMappingURL=";
+                            src += &format!("{}.map", file);
+                        }
+                        _ => {}
+                    }
+
+                    source_map = String::from_utf8(buf).ok();
                 }
 
-                Ok(src)
+                // Registered last: a failed compilation leaves the registries untouched.
+                let classes = classes.take();
+                let class_ids = classes.iter().map(|(id, _)| *id).collect();
+                let registered = classes
+                    .into_iter()
+                    .filter_map(|(id, data)| Some((id, Arc::new(data?))))
+                    .collect();
+                register_file(self.filename.as_deref(), registered, registered_map);
+
+                Ok(CompileResult {
+                    code: src,
+                    source_map,
+                    class_ids,
+                    dependencies,
+                    warnings: warnings.take(),
+                })
             })
         })
     }
@@ -165,8 +548,17 @@ impl Program {
 
 #[cfg(test)]
 mod tests {
-    use crate::parser::{CodeParser, CompileOptions};
+    use super::Program;
+    use crate::parser::{
+        CodeParser, CompileOptions, JsxRuntime, ModuleKind, SourceMapMode, SourceMapOptions,
+    };
+    use crate::reflection::{get_reflection_data, unregister_class, unregister_file};
+    use crate::stack::unregister_source_map;
+    use crate::testing::exec_compiled;
     use crate::testing::uuid::reset_test_uuid;
+    use crate::Severity;
+    use swc_ecma_ast::EsVersion;
+    use uuid::Uuid;
 
     #[test]
     pub fn should_compile_as_function_correctly() -> anyhow::Result<()> {
@@ -181,10 +573,12 @@ export default class TestClass {
 }
 "#;
         let program = code.parse_program(None)?;
-        let code = program.compile(CompileOptions {
-            as_function: true,
-            ..Default::default()
-        })?;
+        let code = program
+            .compile(CompileOptions {
+                as_function: true,
+                ..Default::default()
+            })?
+            .code;
 
         assert_eq!(
             code,
@@ -232,7 +626,7 @@ export default class TestClass {
 }
 "#;
         let program = code.parse_program(None)?;
-        let code = program.compile(Default::default())?;
+        let code = program.compile(Default::default())?.code;
 
         assert_eq!(
             code,
@@ -282,7 +676,7 @@ const parseHosts = (params, dsn) => {};
 export default class RedisAdapter {}
 "#;
         let program = code.parse_program(None).unwrap();
-        let code = program.compile(Default::default()).unwrap();
+        let code = program.compile(Default::default()).unwrap().code;
 
         assert_eq!(
             code,
@@ -336,7 +730,7 @@ class conn {
 }
 "#;
         let program = code.parse_program(None).unwrap();
-        let code = program.compile(Default::default()).unwrap();
+        let code = program.compile(Default::default()).unwrap().code;
 
         assert_eq!(
             code,
@@ -389,7 +783,7 @@ export default class ClassLoaderTest extends TestCase {
 "#
         .parse_program(None)?;
 
-        let compiled = program.compile(Default::default())?;
+        let compiled = program.compile(Default::default())?.code;
 
         assert_eq!(
             compiled,
@@ -441,7 +835,7 @@ export default class YamlFileLoaderTest extends JsonFileLoaderTest {
 "#
         .parse_program(None)?;
 
-        let compiled = program.compile(Default::default())?;
+        let compiled = program.compile(Default::default())?.code;
 
         assert_eq!(
             compiled,
@@ -507,7 +901,7 @@ class TestAnnotation {
 "#
         .parse_program(None)?;
 
-        let compiled = program.compile(Default::default())?;
+        let compiled = program.compile(Default::default())?.code;
 
         assert_eq!(
             compiled,
@@ -583,7 +977,7 @@ export default class Debug {
 "#
         .parse_program(None)?;
 
-        let compiled = program.compile(Default::default())?;
+        let compiled = program.compile(Default::default())?.code;
 
         assert_eq!(
             compiled,
@@ -658,7 +1052,7 @@ export default class FooBar {
 "#
         .parse_program(None)?;
 
-        let compiled = program.compile(Default::default())?;
+        let compiled = program.compile(Default::default())?.code;
 
         assert_eq!(
             compiled,
@@ -773,7 +1167,7 @@ exports.Mutex = Mutex;
 "#
         .parse_program(None)?;
 
-        let compiled = program.compile(Default::default())?;
+        let compiled = program.compile(Default::default())?.code;
 
         assert_eq!(
             compiled,
@@ -903,4 +1297,516 @@ exports.Mutex = _Mutex;
 
         Ok(())
     }
+
+    #[test]
+    pub fn should_compile_jsx_with_classic_runtime() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let code = r#"
+export const Hello = ({ name }) => <div className="hello">Hello {name}<>!</></div>;
+"#;
+
+        let compiled = code
+            .parse_program(Some("hello.jsx"))?
+            .compile(Default::default())?
+            .code;
+        assert!(compiled.contains(r#"React.createElement("div", {"#));
+        assert!(compiled.contains("React.Fragment"));
+
+        let compiled = code
+            .parse_program(Some("hello.jsx"))?
+            .compile(CompileOptions {
+                jsx: JsxRuntime::Classic {
+                    pragma: Some("h".to_string()),
+                    pragma_frag: Some("Fragment".to_string()),
+                },
+                ..Default::default()
+            })?
+            .code;
+        assert!(compiled.contains(r#"h("div", {"#));
+        assert!(compiled.contains("h(Fragment"));
+        assert!(!compiled.contains("React"));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_compile_jsx_with_automatic_runtime() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let code = r#"
+export default function Hello({ name }) {
+    return <div>Hello {name}</div>;
+}
+"#;
+
+        let compiled = code
+            .parse_program(Some("hello.jsx"))?
+            .compile(CompileOptions {
+                jsx: JsxRuntime::Automatic {
+                    import_source: Some("preact".to_string()),
+                },
+                ..Default::default()
+            })?
+            .code;
+        assert!(compiled.contains(r#"require("preact/jsx-runtime")"#));
+        assert!(!compiled.contains("createElement"));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_compile_tsx() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let code = r#"
+const count: number = 1;
+export const el = <span>{count}</span>;
+"#;
+
+        let compiled = code
+            .parse_program(Some("counter.tsx"))?
+            .compile(Default::default())?
+            .code;
+        assert!(compiled.contains(r#"React.createElement("span", null, count)"#));
+        assert!(!compiled.contains(": number"));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_honor_module_kind_override() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let program = "const a = 1;".parse_program(Some("a.cjs"))?;
+        assert!(program.program.is_script());
+
+        let converted = Program::into_module_kind(program.program, Some(ModuleKind::Module))?;
+        assert!(converted.is_module());
+
+        let program = "export const a = 1;".parse_program(None)?;
+        assert!(program
+            .compile(CompileOptions {
+                module_kind: Some(ModuleKind::Script),
+                ..Default::default()
+            })
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_return_compile_result_details() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let code = r#"
+import Redis from 'ioredis' with { optional: true };
+import { parse } from 'url';
+export * from './foo';
+const fs = require('fs');
+const lazy = () => import('./lazy');
+
+export class A {}
+export default class B {}
+"#;
+
+        let result = code
+            .parse_program(Some("deps.js"))?
+            .compile(Default::default())?;
+
+        assert_eq!(
+            result.dependencies,
+            vec!["ioredis", "url", "./foo", "fs", "./lazy"]
+        );
+        assert_eq!(
+            result.class_ids,
+            vec![Uuid::from_u64_pair(0, 0), Uuid::from_u64_pair(0, 1)]
+        );
+        assert!(result
+            .source_map
+            .is_some_and(|m| m.contains(r#""mappings""#)));
+        assert!(result.warnings.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_emit_source_maps_according_to_mode() -> anyhow::Result<()> {
+        let code = "export const a = 1;\n";
+        let compile = |mode| {
+            code.parse_program(Some("src/a.js"))?
+                .compile(CompileOptions {
+                    source_map: SourceMapOptions {
+                        mode,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .map_err(anyhow::Error::from)
+        };
+
+        let inline = compile(SourceMapMode::Inline)?;
+        assert!(inline
+            .code
+            .contains("//# sourceMappingURL=data:application/json;charset=utf-8;base64,"));
+        assert!(inline.source_map.is_some());
+
+        let external = compile(SourceMapMode::External)?;
+        assert!(external.code.ends_with("\n//# sourceMappingURL=a.js.map"));
+        assert!(external.source_map.is_some());
+
+        let hidden = compile(SourceMapMode::Hidden)?;
+        assert!(!hidden.code.contains("sourceMappingURL"));
+        assert!(hidden.source_map.is_some());
+
+        let none = compile(SourceMapMode::None)?;
+        assert!(!none.code.contains("sourceMappingURL"));
+        assert!(none.source_map.is_none());
+
+        let no_filename = code.parse_program(None)?.compile(CompileOptions {
+            source_map: SourceMapOptions {
+                mode: SourceMapMode::External,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        assert!(no_filename.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_apply_source_map_options() -> anyhow::Result<()> {
+        let code = "export const a = 1;\n";
+        let result = code
+            .parse_program(Some("src/a.js"))?
+            .compile(CompileOptions {
+                source_map: SourceMapOptions {
+                    mode: SourceMapMode::External,
+                    source_root: Some("/project".to_string()),
+                    sources_content: true,
+                    file: Some("a.min.js".to_string()),
+                    source_path: Some("../src/a.js".to_string()),
+                },
+                ..Default::default()
+            })?;
+
+        assert!(result.code.ends_with("//# sourceMappingURL=a.min.js.map"));
+
+        let source_map = result.source_map.unwrap();
+        assert!(source_map.contains(r#""sources":["../src/a.js"]"#));
+
+        let map = sourcemap::SourceMap::from_slice(source_map.as_bytes())?;
+        assert_eq!(map.get_file(), Some("a.min.js"));
+        assert_eq!(map.get_source_root(), Some("/project"));
+        assert_eq!(map.get_source_contents(0), Some(code));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_downlevel_syntax_unsupported_by_target() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let code = r#"
+let a = b ?? c;
+a ||= d?.e;
+try { a(); } catch { }
+async function fetch() { await a(); }
+async function* stream() { yield await a(); }
+export default class A {
+    static { a(); }
+    #priv = 1;
+}
+"#;
+        let compile = |target| {
+            code.parse_program(Some("target.js"))?
+                .compile(CompileOptions {
+                    target,
+                    source_map: SourceMapOptions {
+                        mode: SourceMapMode::None,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .map(|r| r.code)
+                .map_err(anyhow::Error::from)
+        };
+
+        let esnext = compile(Some(EsVersion::EsNext))?;
+        assert!(esnext.contains("??"));
+        assert!(esnext.contains("||="));
+        assert!(esnext.contains("static{"));
+        assert!(esnext.contains("#priv = _init_priv(this, 1)"));
+
+        let legacy = compile(None)?;
+        assert!(!legacy.contains("??"));
+        assert!(legacy.contains("||="));
+        assert!(!legacy.contains("static{"));
+        assert!(legacy.contains("#priv = _init_priv(this, 1)"));
+
+        let es2018 = compile(Some(EsVersion::Es2018))?;
+        assert!(!es2018.contains("??"));
+        assert!(!es2018.contains("||="));
+        assert!(!es2018.contains("static{"));
+        assert!(!es2018.contains("#priv"));
+        assert!(es2018.contains("catch (e)"));
+
+        let es2017 = compile(Some(EsVersion::Es2017))?;
+        assert!(es2017.contains("async function fetch()"));
+        assert!(!es2017.contains("async function*"));
+
+        assert!(compile(Some(EsVersion::Es2016)).is_err());
+        assert!(compile(Some(EsVersion::Es5)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_execute_async_iteration_lowered_for_es2017() -> anyhow::Result<()> {
+        let code = r#"
+async function* range(n) {
+    for (let i = 0; i < n; i++) {
+        yield await i;
+    }
+}
+
+export async function sum(n) {
+    let total = 0;
+    for await (const i of range(n)) {
+        total += i;
+    }
+
+    return total;
+}
+
+export async function double(n) {
+    return 2 * await n;
+}
+"#;
+
+        let result = code.parse_program(Some("x.js"))?.compile(CompileOptions {
+            target: Some(EsVersion::Es2017),
+            source_map: SourceMapOptions {
+                mode: SourceMapMode::None,
+                ..Default::default()
+            },
+            ..Default::default()
+        })?;
+
+        assert!(!result.code.contains("for await"));
+        assert!(!result.code.contains("async function*"));
+        assert!(result.code.contains("async function double(n)"));
+
+        exec_compiled(
+            "es2017",
+            &result.code,
+            r#"
+assert.strictEqual(await exports.sum(4), 6);
+assert.strictEqual(await exports.double(21), 42);
+"#,
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_minify_keeping_reflection_names() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let code = r#"
+export default class LongClassName {
+    method(firstArgument, secondArgument) {
+        const result = firstArgument + secondArgument;
+        return result;
+    }
+}
+
+export const factory = function () {
+    return new LongClassName();
+};
+"#;
+
+        let result = code
+            .parse_program(Some("minify.js"))?
+            .compile(CompileOptions {
+                minify: true,
+                source_map: SourceMapOptions {
+                    mode: SourceMapMode::Hidden,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })?;
+
+        assert!(!result.code.contains("firstArgument"));
+        assert!(result.code.contains("class LongClassName"));
+        assert!(result.code.contains("function factory()"));
+        assert!(result
+            .code
+            .contains(r#"__jymfony_reflect("00000000-0000-0000-0000-000000000000""#));
+
+        let map = sourcemap::SourceMap::from_slice(result.source_map.unwrap().as_bytes())?;
+        assert!(map.get_token_count() > 0);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_execute_minified_code() -> anyhow::Result<()> {
+        let code = r#"
+const names = [];
+const call = (fn) => names.push(fn.name);
+call(function () {});
+
+export const x = [class {}];
+
+export default class LongClassName {
+    method(firstArgument, secondArgument) {
+        return firstArgument + secondArgument;
+    }
+}
+
+export const factory = function () {
+    return new LongClassName();
+};
+
+export { names };
+"#;
+
+        let result = code.parse_program(Some("x.js"))?.compile(CompileOptions {
+            minify: true,
+            source_map: SourceMapOptions {
+                mode: SourceMapMode::None,
+                ..Default::default()
+            },
+            ..Default::default()
+        })?;
+
+        exec_compiled(
+            "minify",
+            &result.code,
+            r#"
+assert.strictEqual(typeof exports.x[0], 'function');
+assert.ok(new exports.x[0]() instanceof __jymfony.JObject);
+assert.strictEqual(exports.factory.name, 'factory');
+assert.ok(exports.factory() instanceof exports.default);
+assert.strictEqual(exports.factory().method(1, 2), 3);
+assert.strictEqual(exports.default.name, 'LongClassName');
+assert.strictEqual(exports.names.length, 1);
+assert.ok(exports.names[0].startsWith('_anonymous_x'));
+"#,
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_generate_deterministic_class_ids() -> anyhow::Result<()> {
+        let code = r#"
+export default class A {}
+export const b = class {};
+export const c = class {};
+"#;
+        let compile = |namespace: Option<&str>| {
+            code.parse_program(Some("deterministic.js"))?
+                .compile(CompileOptions {
+                    namespace: namespace.map(|n| n.to_string()),
+                    deterministic_ids: true,
+                    ..Default::default()
+                })
+                .map_err(anyhow::Error::from)
+        };
+
+        let first = compile(None)?;
+        let second = compile(None)?;
+        assert_eq!(first.class_ids, second.class_ids);
+        assert_eq!(first.class_ids.len(), 3);
+        assert!(first.class_ids.iter().all(|id| id.get_version_num() == 5));
+        assert_ne!(first.class_ids[1], first.class_ids[2]);
+
+        let namespaced = compile(Some("App"))?;
+        assert_ne!(first.class_ids[0], namespaced.class_ids[0]);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_replace_classes_of_recompiled_files() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let compile = |code: &str| {
+            code.parse_program(Some("hot-reload.js"))?
+                .compile(Default::default())
+                .map_err(anyhow::Error::from)
+        };
+
+        let first = compile("export class A {}\nexport class B {}\n")?;
+        let second = compile("export class A {}\n")?;
+        assert!(first
+            .class_ids
+            .iter()
+            .all(|id| get_reflection_data(id).is_none()));
+        assert_eq!(get_reflection_data(&second.class_ids[0]).unwrap().name, "A");
+
+        let other = "export class C {}"
+            .parse_program(None)?
+            .compile(Default::default())?;
+        assert!(unregister_class(&other.class_ids[0]));
+        assert!(!unregister_class(&other.class_ids[0]));
+
+        assert_eq!(unregister_file("hot-reload.js"), second.class_ids);
+        assert!(get_reflection_data(&second.class_ids[0]).is_none());
+        assert!(unregister_source_map("hot-reload.js"));
+        assert!(!unregister_source_map("hot-reload.js"));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_read_namespaces_from_docblocks() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let code = r#"
+/** @memberOf App.Entity */
+export class User {}
+
+/** @namespace App.Invalid-Name */
+export class Invalid {}
+
+export class Plain {}
+"#;
+
+        let result = code
+            .parse_program(Some("namespaces.js"))?
+            .compile(CompileOptions {
+                namespace: Some("Other".to_string()),
+                ..Default::default()
+            })?;
+
+        let namespace = |idx: usize| {
+            get_reflection_data(&result.class_ids[idx])
+                .unwrap()
+                .namespace
+                .clone()
+        };
+        assert_eq!(namespace(0).as_deref(), Some("App.Entity"));
+        assert_eq!(namespace(1).as_deref(), Some("Other"));
+        assert_eq!(namespace(2).as_deref(), Some("Other"));
+
+        let kinds = result
+            .warnings
+            .iter()
+            .map(|w| (w.kind.as_str(), w.severity, w.line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                ("NamespaceConflict", Severity::Warning, 3),
+                ("InvalidNamespace", Severity::Warning, 6),
+            ]
+        );
+        assert!(result.warnings[1].message.contains("\"Invalid-Name\""));
+
+        Ok(())
+    }
 }
@@ -2,6 +2,7 @@ use anyhow::{bail, Context, Error};
 use base64::prelude::*;
 use std::fs::File;
 use std::path::PathBuf;
+use swc_common::source_map::SourceMapGenConfig;
 use swc_common::{FileName, SourceFile};
 use url::Url;
 
@@ -135,3 +136,18 @@ pub fn get_orig_src_map(fm: &SourceFile) -> Result<Option<sourcemap::SourceMap>,
         }
     })
 }
+
+/// Source map generation configuration.
+pub(crate) struct SourceMapConfig {
+    pub sources_content: bool,
+}
+
+impl SourceMapGenConfig for SourceMapConfig {
+    fn file_name_to_source(&self, f: &FileName) -> String {
+        f.to_string()
+    }
+
+    fn inline_sources_content(&self, _: &FileName) -> bool {
+        self.sources_content
+    }
+}
@@ -1,32 +1,92 @@
-#[cfg(not(test))]
-use rand::prelude::*;
+use crate::parser::is_valid_identifier;
+use std::path::Path;
 use swc_ecma_ast::*;
 use swc_ecma_utils::private_ident;
 use swc_ecma_visit::{as_folder, noop_visit_mut_type, Fold, VisitMut, VisitMutWith};
 
-pub fn anonymous_expr() -> impl VisitMut + Fold {
-    as_folder(AnonymousExpr::default())
+/// The prefix of the names given to the expressions whose name cannot be inferred.
+pub(crate) const ANONYMOUS_PREFIX: &str = "_anonymous_xΞ";
+
+/// Names the anonymous class and function expressions.
+/// Names are inferred from the context (variable declarations, object keys,
+/// assignment targets and default exports); a counter is used otherwise.
+pub fn anonymous_expr(filename: Option<&str>) -> impl VisitMut + Fold {
+    as_folder(AnonymousExpr {
+        default_name: filename
+            .and_then(|f| Path::new(f).file_stem())
+            .and_then(|s| to_identifier(&s.to_string_lossy())),
+        counter: 0,
+    })
 }
 
-#[derive(Default)]
 struct AnonymousExpr {
-    #[cfg(test)]
-    test_current_id: i32,
+    /// The name of the default export, inferred from the file stem.
+    default_name: Option<String>,
+    counter: u32,
+}
+
+/// Converts a property key or a file stem into a valid identifier, if possible.
+fn to_identifier(name: &str) -> Option<String> {
+    let mut ident = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '$' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+
+    is_valid_identifier(&ident).then_some(ident)
 }
 
 impl AnonymousExpr {
     fn gen_anonymous_ident(&mut self) -> Ident {
-        #[cfg(not(test))]
-        let rnd = thread_rng().gen_range(0..1000000);
-        #[cfg(test)]
-        let rnd = {
-            self.test_current_id += 1;
-            self.test_current_id
+        self.counter += 1;
+        let ident = format!("{}{:X}", ANONYMOUS_PREFIX, self.counter);
+
+        private_ident!(ident)
+    }
+
+    /// Names the expression after the given name, if it is an anonymous class or function.
+    fn name_expr(&mut self, expr: &mut Expr, name: &str) {
+        let ident = match expr {
+            Expr::Paren(p) => return self.name_expr(&mut p.expr, name),
+            Expr::Class(ClassExpr { ident, .. }) | Expr::Fn(FnExpr { ident, .. }) => ident,
+            _ => return,
         };
 
-        let ident = format!("_anonymous_xΞ{:X}", rnd);
+        if ident.is_none() {
+            *ident = to_identifier(name).map(|name| private_ident!(name));
+        }
+    }
 
-        private_ident!(ident)
+    /// The name inferred from an assignment target.
+    fn target_name(&self, target: &Expr) -> Option<String> {
+        match target {
+            Expr::Ident(i) => Some(i.sym.to_string()),
+            Expr::Member(m) => {
+                let is_module_exports = matches!((&*m.obj, &m.prop), (Expr::Ident(o), MemberProp::Ident(p)) if &*o.sym == "module" && &*p.sym == "exports");
+                if is_module_exports {
+                    return self.default_name.clone();
+                }
+
+                match &m.prop {
+                    MemberProp::Ident(i) => Some(i.sym.to_string()),
+                    MemberProp::Computed(c) => match c.expr.as_lit() {
+                        Some(Lit::Str(s)) => Some(s.value.to_string()),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
     }
 }
 
@@ -48,4 +108,106 @@ impl VisitMut for AnonymousExpr {
             n.ident = Some(self.gen_anonymous_ident());
         }
     }
+
+    fn visit_mut_var_declarator(&mut self, n: &mut VarDeclarator) {
+        if let (Pat::Ident(binding), Some(init)) = (&n.name, n.init.as_deref_mut()) {
+            self.name_expr(init, &binding.id.sym);
+        }
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_key_value_prop(&mut self, n: &mut KeyValueProp) {
+        let name = match &n.key {
+            PropName::Ident(i) => Some(i.sym.to_string()),
+            PropName::Str(s) => Some(s.value.to_string()),
+            _ => None,
+        };
+
+        if let Some(name) = name {
+            self.name_expr(&mut n.value, &name);
+        }
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_assign_expr(&mut self, n: &mut AssignExpr) {
+        if n.op == op!("=") {
+            let name = match &n.left {
+                PatOrExpr::Expr(e) => self.target_name(e),
+                PatOrExpr::Pat(p) => match p.as_ref() {
+                    Pat::Ident(i) => Some(i.id.sym.to_string()),
+                    Pat::Expr(e) => self.target_name(e),
+                    _ => None,
+                },
+            };
+
+            if let Some(name) = name {
+                self.name_expr(&mut n.right, &name);
+            }
+        }
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_export_default_decl(&mut self, n: &mut ExportDefaultDecl) {
+        if let Some(name) = self.default_name.clone() {
+            match &mut n.decl {
+                DefaultDecl::Class(ClassExpr { ident, .. })
+                | DefaultDecl::Fn(FnExpr { ident, .. })
+                    if ident.is_none() =>
+                {
+                    *ident = Some(private_ident!(name));
+                }
+                _ => {}
+            }
+        }
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_export_default_expr(&mut self, n: &mut ExportDefaultExpr) {
+        if let Some(name) = self.default_name.clone() {
+            self.name_expr(&mut n.expr, &name);
+        }
+
+        n.visit_mut_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::anonymous_expr;
+    use crate::testing::compile_tr;
+
+    #[test]
+    pub fn should_infer_names_from_context() {
+        let code = r#"
+const Foo = class {};
+const bar = function () {};
+const obj = { baz: class {}, 'qux-key': function () {} };
+obj.assigned = class {};
+module.exports = class {};
+call(class {}, function () {});
+"#;
+
+        let compiled = compile_tr(|_| anonymous_expr(Some("src/user-service.js")), code);
+        assert!(compiled.contains("const Foo = class Foo {"));
+        assert!(compiled.contains("function bar()"));
+        assert!(compiled.contains("baz: class baz {"));
+        assert!(compiled.contains("function qux_key()"));
+        assert!(compiled.contains("obj.assigned = class assigned {"));
+        assert!(compiled.contains("module.exports = class user_service {"));
+        assert!(compiled.contains("call(class _anonymous_xΞ1 {"));
+        assert!(compiled.contains("function _anonymous_xΞ2()"));
+    }
+
+    #[test]
+    pub fn should_name_default_exports_after_the_file() {
+        let compiled = compile_tr(
+            |_| anonymous_expr(Some("lib/0-index.mjs")),
+            "export default class {}",
+        );
+        assert!(compiled.contains("export default class _0_index {"));
+    }
 }
@@ -0,0 +1,88 @@
+use swc_common::comments::Comments;
+use swc_common::{chain, Mark, Span};
+use swc_ecma_ast::*;
+use swc_ecma_transforms_compat::es2017::async_to_generator;
+use swc_ecma_visit::{
+    as_folder, noop_visit_mut_type, noop_visit_type, Fold, Visit, VisitMut, VisitMutWith,
+    VisitWith,
+};
+
+/// Lowers async generator functions and `for await` loops, leaving the other async
+/// functions untouched. `async_to_generator` rewrites both: plain async functions are
+/// hidden from it (marked and made synchronous) and restored once it has run.
+/// The async functions containing a `for await` loop are not hidden, as the loop is
+/// lowered along with the function.
+pub fn async_generators<C: Comments + Clone>(
+    comments: Option<C>,
+    unresolved_mark: Mark,
+) -> impl Fold {
+    let mark = Mark::new();
+
+    chain!(
+        as_folder(AsyncFunctions { mark, hide: true }),
+        async_to_generator(Default::default(), comments, unresolved_mark),
+        as_folder(AsyncFunctions { mark, hide: false }),
+    )
+}
+
+struct AsyncFunctions {
+    mark: Mark,
+    hide: bool,
+}
+
+impl AsyncFunctions {
+    fn toggle(&self, is_async: &mut bool, is_generator: bool, span: &mut Span) {
+        if self.hide {
+            if *is_async && !is_generator {
+                *is_async = false;
+                *span = span.apply_mark(self.mark);
+            }
+        } else if span.ctxt.outer() == self.mark {
+            *is_async = true;
+            span.ctxt.remove_mark();
+        }
+    }
+}
+
+impl VisitMut for AsyncFunctions {
+    noop_visit_mut_type!();
+
+    fn visit_mut_function(&mut self, n: &mut Function) {
+        n.visit_mut_children_with(self);
+        if !(self.hide && contains_for_await(&n.body)) {
+            self.toggle(&mut n.is_async, n.is_generator, &mut n.span);
+        }
+    }
+
+    fn visit_mut_arrow_expr(&mut self, n: &mut ArrowExpr) {
+        n.visit_mut_children_with(self);
+        if !(self.hide && contains_for_await(&n.body)) {
+            self.toggle(&mut n.is_async, n.is_generator, &mut n.span);
+        }
+    }
+}
+
+/// Whether a function body contains a `for await` loop, nested functions excluded.
+fn contains_for_await<N: VisitWith<ForAwaitFinder>>(body: &N) -> bool {
+    let mut finder = ForAwaitFinder { found: false };
+    body.visit_with(&mut finder);
+
+    finder.found
+}
+
+struct ForAwaitFinder {
+    found: bool,
+}
+
+impl Visit for ForAwaitFinder {
+    noop_visit_type!();
+
+    fn visit_for_of_stmt(&mut self, n: &ForOfStmt) {
+        self.found |= n.is_await;
+        n.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+}
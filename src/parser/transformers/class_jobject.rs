@@ -6,7 +6,7 @@ use swc_ecma_ast::*;
 use swc_ecma_visit::{as_folder, noop_visit_mut_type, Fold, VisitMut, VisitMutWith};
 
 lazy_static! {
-    static ref JOBJECT_ACCESSOR: MemberExpr = {
+    pub(crate) static ref JOBJECT_ACCESSOR: MemberExpr = {
         let obj_expr = ident("__jymfony");
         let prop = ident("JObject");
 
@@ -1,30 +1,65 @@
+use super::ANONYMOUS_PREFIX;
 use crate::generate_uuid;
-use crate::parser::util::ident;
-use crate::reflection::{register_class, ReflectionData};
+use crate::parser::util::{ident, json_to_expr};
+use crate::parser::{is_valid_identifier, CompileOptions, ReflectionMode};
+use crate::reflection::{
+    collect_imports, process_reflection_data, Docblock, Imports, ReflectionData,
+};
+use crate::Diagnostic;
 use rustc_hash::FxHashMap;
+use std::cell::RefCell;
 use std::rc::Rc;
 use swc_common::comments::{CommentKind, Comments};
-use swc_common::{Span, Spanned, DUMMY_SP};
+use swc_common::sync::Lrc;
+use swc_common::{SourceMap, Span, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_utils::{undefined, ExprFactory};
 use swc_ecma_visit::{as_folder, noop_visit_mut_type, Fold, VisitMut, VisitMutWith};
+use uuid::Uuid;
+
+/// A processed class and its reflection data, registered once the compilation succeeds.
+/// The data is `None` if it has been inlined into the compiled code.
+pub(crate) type CollectedClass = (Uuid, Option<ReflectionData>);
+
+/// The namespace of the name-based class ids.
+const CLASS_ID_NAMESPACE: Uuid = Uuid::from_u128(0x2d3f6a1e_9c4b_5f07_8e21_b6d40a93c715);
 
 pub fn class_reflection_decorators<'a, C: Comments + 'a>(
     filename: Option<&'a str>,
-    namespace: Option<&'a str>,
+    opts: &CompileOptions,
     comments: Rc<C>,
+    source_map: Lrc<SourceMap>,
+    classes: Rc<RefCell<Vec<CollectedClass>>>,
+    warnings: Rc<RefCell<Vec<Diagnostic>>>,
 ) -> impl VisitMut + Fold + 'a {
     as_folder(ClassReflectionDecorators {
         filename,
-        namespace,
+        namespace: opts.namespace.clone(),
         comments,
+        source_map,
+        classes,
+        warnings,
+        deterministic_ids: opts.deterministic_ids,
+        inline: opts.reflection == ReflectionMode::Inline,
+        imports: Default::default(),
+        class_count: 0,
     })
 }
 
 struct ClassReflectionDecorators<'a, C: Comments> {
     filename: Option<&'a str>,
-    namespace: Option<&'a str>,
+    namespace: Option<String>,
     comments: Rc<C>,
+    source_map: Lrc<SourceMap>,
+    classes: Rc<RefCell<Vec<CollectedClass>>>,
+    warnings: Rc<RefCell<Vec<Diagnostic>>>,
+    deterministic_ids: bool,
+    /// Embeds the processed reflection data into the class decorator call
+    /// instead of registering it.
+    inline: bool,
+    /// The bindings imported by the module, resolving the types referenced by annotations.
+    imports: Imports,
+    class_count: usize,
 }
 
 impl<'a, C: Comments> ClassReflectionDecorators<'a, C> {
@@ -43,8 +78,84 @@ impl<'a, C: Comments> ClassReflectionDecorators<'a, C> {
             })
     }
 
-    fn process_class(&self, n: &mut Class, name: Ident, outer_docblock: Option<String>) {
-        let id = generate_uuid();
+    /// Generates the id of a class: random, unless deterministic ids are requested.
+    /// Deterministic ids are derived from the filename, the namespace, the class name
+    /// and its position; the ordinal of the class in the file tells anonymous classes apart.
+    fn generate_class_id(&mut self, n: &Class, name: &Ident, namespace: Option<&str>) -> Uuid {
+        self.class_count += 1;
+        if !self.deterministic_ids {
+            return generate_uuid();
+        }
+
+        let class_name = if name.sym.starts_with(ANONYMOUS_PREFIX) {
+            ""
+        } else {
+            &*name.sym
+        };
+
+        let key = format!(
+            "{}\0{}\0{}\0{}\0{}",
+            self.filename.unwrap_or_default(),
+            namespace.unwrap_or_default(),
+            class_name,
+            n.span.lo.0,
+            self.class_count,
+        );
+
+        Uuid::new_v5(&CLASS_ID_NAMESPACE, key.as_bytes())
+    }
+
+    fn warn(&self, span: Span, kind: &str, message: String) {
+        if span.is_dummy() {
+            return;
+        }
+
+        let warning = Diagnostic::warning(&self.source_map, span, self.filename, kind, message);
+        self.warnings.borrow_mut().push(warning);
+    }
+
+    /// The namespace of a class: the one declared by its `@memberOf` or `@namespace`
+    /// docblock tag, the namespace of the compile options otherwise.
+    fn class_namespace(&self, span: Span, docblock: Option<&str>) -> Option<String> {
+        let declared = docblock.map(Docblock::parse).and_then(|d| {
+            d.tags
+                .into_iter()
+                .find(|t| matches!(t.tag.as_str(), "memberOf" | "memberof" | "namespace"))
+                .and_then(|t| t.name)
+        });
+
+        let Some(declared) = declared else {
+            return self.namespace.clone();
+        };
+
+        if let Some(segment) = declared.split('.').find(|s| !is_valid_identifier(s)) {
+            self.warn(
+                span,
+                "InvalidNamespace",
+                format!(
+                    "Ignoring namespace \"{}\" declared in the docblock: \"{}\" is not a valid identifier",
+                    declared, segment
+                ),
+            );
+
+            return self.namespace.clone();
+        }
+
+        if let Some(namespace) = self.namespace.as_deref().filter(|ns| *ns != declared) {
+            self.warn(
+                span,
+                "NamespaceConflict",
+                format!(
+                    "Namespace \"{}\" declared in the docblock overrides the \"{}\" namespace of the compile options",
+                    declared, namespace
+                ),
+            );
+        }
+
+        Some(declared)
+    }
+
+    fn process_class(&mut self, n: &mut Class, name: Ident, outer_docblock: Option<String>) {
         let mut docblock = FxHashMap::default();
         if let Some(outer_db) = outer_docblock {
             docblock.insert(n.span, Some(outer_db));
@@ -56,23 +167,9 @@ impl<'a, C: Comments> ClassReflectionDecorators<'a, C> {
             }
         }
 
-        n.decorators.push(Decorator {
-            span: DUMMY_SP,
-            expr: Box::new(Expr::Call(CallExpr {
-                span: DUMMY_SP,
-                callee: ident("__jymfony_reflect").as_callee(),
-                args: vec![
-                    id.to_string().as_arg(),
-                    n.body
-                        .iter()
-                        .enumerate()
-                        .find(|(_, m)| matches!(m, ClassMember::Constructor(_)))
-                        .map(|(idx, _)| idx.as_arg())
-                        .unwrap_or_else(|| undefined(DUMMY_SP).as_arg()),
-                ],
-                type_args: None,
-            })),
-        });
+        let class_docblock = docblock.get(&n.span).cloned().flatten();
+        let namespace = self.class_namespace(n.span, class_docblock.as_deref());
+        let id = self.generate_class_id(n, &name, namespace.as_deref());
 
         for (idx, member) in n.body.iter_mut().enumerate() {
             let reflect_ident = Expr::Call(CallExpr {
@@ -124,16 +221,55 @@ impl<'a, C: Comments> ClassReflectionDecorators<'a, C> {
             }
         }
 
-        register_class(
-            &id,
-            ReflectionData::new(n, name, self.filename, self.namespace, docblock),
+        let data = ReflectionData::new(
+            n,
+            &name,
+            self.filename,
+            namespace.as_deref(),
+            docblock,
+            &self.imports,
         );
+        let mut args = vec![
+            id.to_string().as_arg(),
+            n.body
+                .iter()
+                .enumerate()
+                .find(|(_, m)| matches!(m, ClassMember::Constructor(_)))
+                .map(|(idx, _)| idx.as_arg())
+                .unwrap_or_else(|| undefined(DUMMY_SP).as_arg()),
+        ];
+
+        let data = if self.inline {
+            let processed = serde_json::to_value(process_reflection_data(&data))
+                .expect("reflection data should be serializable");
+            args.push(json_to_expr(&processed).as_arg());
+
+            None
+        } else {
+            Some(data)
+        };
+
+        n.decorators.push(Decorator {
+            span: DUMMY_SP,
+            expr: Box::new(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: ident("__jymfony_reflect").as_callee(),
+                args,
+                type_args: None,
+            })),
+        });
+        self.classes.borrow_mut().push((id, data));
     }
 }
 
 impl<C: Comments> VisitMut for ClassReflectionDecorators<'_, C> {
     noop_visit_mut_type!();
 
+    fn visit_mut_module(&mut self, n: &mut Module) {
+        self.imports = collect_imports(n);
+        n.visit_mut_children_with(self);
+    }
+
     fn visit_mut_module_item(&mut self, n: &mut ModuleItem) {
         match n {
             ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
@@ -182,19 +318,35 @@ impl<C: Comments> VisitMut for ClassReflectionDecorators<'_, C> {
 #[cfg(test)]
 mod tests {
     use crate::parser::transformers::class_reflection_decorators;
+    use crate::parser::{CompileOptions, ReflectionMode};
     use crate::testing::compile_tr;
     use swc_common::{chain, Mark};
     use swc_ecma_transforms_base::resolver;
     use swc_ecma_transforms_testing::Tester;
     use swc_ecma_visit::Fold;
 
-    fn create_pass(tester: &mut Tester) -> Box<dyn Fold> {
+    fn create_pass(tester: &mut Tester, inline: bool) -> Box<dyn Fold> {
         let unresolved_mark = Mark::new();
         let top_level_mark = Mark::new();
+        let opts = CompileOptions {
+            reflection: if inline {
+                ReflectionMode::Inline
+            } else {
+                ReflectionMode::Registry
+            },
+            ..Default::default()
+        };
 
         Box::new(chain!(
             resolver(unresolved_mark, top_level_mark, false),
-            class_reflection_decorators(None, None, tester.comments.clone()),
+            class_reflection_decorators(
+                None,
+                &opts,
+                tester.comments.clone(),
+                tester.cm.clone(),
+                Default::default(),
+                Default::default(),
+            ),
         ))
     }
 
@@ -208,7 +360,7 @@ export default class TestClass {
 }
 "#;
 
-        let compiled = compile_tr(|tester| create_pass(tester), code);
+        let compiled = compile_tr(|tester| create_pass(tester, false), code);
         assert_eq!(
             compiled,
             r#"export default @__jymfony_reflect("00000000-0000-0000-0000-000000000000", void 0)
@@ -221,4 +373,27 @@ class TestClass {
 "#
         );
     }
+
+    #[test]
+    pub fn should_inline_reflection_data() {
+        let code = r#"
+/** Class docblock */
+export default class TestClass {
+    publicMethod(a, b = 12) {
+    }
+}
+"#;
+
+        let compiled = compile_tr(|tester| create_pass(tester, true), code);
+        assert!(compiled
+            .contains(r#"@__jymfony_reflect("00000000-0000-0000-0000-000000000000", void 0, {"#));
+        assert!(compiled.contains(r#"fqcn: "TestClass""#));
+        assert!(compiled.contains(r#"docblock: "/** Class docblock */""#));
+        assert!(compiled.contains(r#"kind: "method""#));
+        assert!(compiled.contains(r#"scalarDefault: {"#));
+        assert!(
+            compiled.contains(r#"@__jymfony_reflect("00000000-0000-0000-0000-000000000000", 0)"#)
+        );
+        assert!(!compiled.contains("namespace:"));
+    }
 }
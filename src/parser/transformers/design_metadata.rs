@@ -0,0 +1,519 @@
+use crate::parser::util::ident;
+use crate::reflection::pat_type_ann;
+use rustc_hash::{FxHashMap, FxHashSet};
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_utils::{quote_str, undefined, ExprFactory};
+use swc_ecma_visit::{
+    as_folder, noop_visit_mut_type, Fold, Visit, VisitMut, VisitMutWith, VisitWith,
+};
+
+/// Emits the design-time types of decorated classes and members, as TypeScript's
+/// `emitDecoratorMetadata` does, through `__jymfony_metadata` decorators which store
+/// them into the decorator `context.metadata`.
+/// Must run before `strip`: the emitted references keep the imports they use.
+pub fn design_metadata() -> impl VisitMut + Fold {
+    as_folder(DesignMetadata::default())
+}
+
+#[derive(Default)]
+struct DesignMetadata {
+    enums: FxHashMap<Id, DesignType>,
+}
+
+/// The runtime value a type is serialized to.
+#[derive(Clone)]
+enum DesignType {
+    Void,
+    Global(&'static str),
+    /// An entity name (ie: `Foo` or `ns.Foo`), which may not exist at runtime.
+    Reference(Vec<Ident>),
+}
+
+impl PartialEq for DesignType {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DesignType::Void, DesignType::Void) => true,
+            (DesignType::Global(a), DesignType::Global(b)) => a == b,
+            (DesignType::Reference(a), DesignType::Reference(b)) => {
+                a.iter().map(Ident::to_id).eq(b.iter().map(Ident::to_id))
+            }
+            _ => false,
+        }
+    }
+}
+
+fn entity_path(name: &TsEntityName) -> Vec<Ident> {
+    match name {
+        TsEntityName::Ident(i) => vec![i.clone()],
+        TsEntityName::TsQualifiedName(q) => {
+            let mut path = entity_path(&q.left);
+            path.push(q.right.clone());
+            path
+        }
+    }
+}
+
+/// The enums declared in the program, serialized as TypeScript does:
+/// `String` if all their members are strings, `Number` if none is, `Object` otherwise.
+#[derive(Default)]
+struct EnumCollector {
+    enums: FxHashMap<Id, DesignType>,
+}
+
+impl Visit for EnumCollector {
+    fn visit_ts_enum_decl(&mut self, n: &TsEnumDecl) {
+        let is_string = |m: &TsEnumMember| {
+            matches!(
+                m.init.as_deref(),
+                Some(Expr::Lit(Lit::Str(_)) | Expr::Tpl(_))
+            )
+        };
+
+        let ty = if n.members.iter().all(is_string) {
+            DesignType::Global("String")
+        } else if n.members.iter().any(is_string) {
+            DesignType::Global("Object")
+        } else {
+            DesignType::Global("Number")
+        };
+
+        self.enums.insert(n.id.to_id(), ty);
+    }
+}
+
+/// The declarations types are serialized against: the enums of the program
+/// and the type parameters of the enclosing class and method.
+#[derive(Clone)]
+struct TypeScope<'a> {
+    enums: &'a FxHashMap<Id, DesignType>,
+    type_params: FxHashSet<Id>,
+}
+
+impl<'a> TypeScope<'a> {
+    fn new(enums: &'a FxHashMap<Id, DesignType>, type_params: Option<&TsTypeParamDecl>) -> Self {
+        Self {
+            enums,
+            type_params: Default::default(),
+        }
+        .with_type_params(type_params)
+    }
+
+    fn with_type_params(mut self, type_params: Option<&TsTypeParamDecl>) -> Self {
+        let params = type_params.into_iter().flat_map(|d| d.params.iter());
+        self.type_params.extend(params.map(|p| p.name.to_id()));
+        self
+    }
+
+    /// Type parameters are serialized to `Object`, enums (and their members)
+    /// to the type of their values.
+    fn serialize_reference(&self, name: &TsEntityName) -> DesignType {
+        let path = entity_path(name);
+        let root = path[0].to_id();
+        if path.len() == 1 && self.type_params.contains(&root) {
+            return DesignType::Global("Object");
+        }
+
+        match self.enums.get(&root) {
+            Some(ty) if path.len() <= 2 => ty.clone(),
+            _ => DesignType::Reference(path),
+        }
+    }
+
+    /// Serializes a union or an intersection: the common type of its members,
+    /// `null`, `undefined` and `never` excluded, `Object` if they differ.
+    fn serialize_union(&self, types: &[Box<TsType>]) -> DesignType {
+        let mut serialized: Option<DesignType> = None;
+        for ty in types {
+            let ty = self.serialize_type(ty);
+            if ty == DesignType::Void {
+                continue;
+            }
+
+            match &serialized {
+                Some(s) if *s != ty => return DesignType::Global("Object"),
+                _ => serialized = Some(ty),
+            }
+        }
+
+        serialized.unwrap_or(DesignType::Void)
+    }
+
+    fn serialize_type(&self, ty: &TsType) -> DesignType {
+        match ty {
+            TsType::TsKeywordType(k) => match k.kind {
+                TsKeywordTypeKind::TsVoidKeyword
+                | TsKeywordTypeKind::TsUndefinedKeyword
+                | TsKeywordTypeKind::TsNullKeyword
+                | TsKeywordTypeKind::TsNeverKeyword => DesignType::Void,
+                TsKeywordTypeKind::TsNumberKeyword => DesignType::Global("Number"),
+                TsKeywordTypeKind::TsStringKeyword => DesignType::Global("String"),
+                TsKeywordTypeKind::TsBooleanKeyword => DesignType::Global("Boolean"),
+                TsKeywordTypeKind::TsBigIntKeyword => DesignType::Global("BigInt"),
+                TsKeywordTypeKind::TsSymbolKeyword => DesignType::Global("Symbol"),
+                _ => DesignType::Global("Object"),
+            },
+            TsType::TsFnOrConstructorType(_) => DesignType::Global("Function"),
+            TsType::TsArrayType(_) | TsType::TsTupleType(_) => DesignType::Global("Array"),
+            TsType::TsTypePredicate(p) if !p.asserts => DesignType::Global("Boolean"),
+            TsType::TsTypeRef(r) => self.serialize_reference(&r.type_name),
+            TsType::TsParenthesizedType(p) => self.serialize_type(&p.type_ann),
+            TsType::TsOptionalType(o) => self.serialize_type(&o.type_ann),
+            TsType::TsTypeOperator(o) => match o.op {
+                TsTypeOperatorOp::ReadOnly => self.serialize_type(&o.type_ann),
+                TsTypeOperatorOp::Unique => DesignType::Global("Symbol"),
+                TsTypeOperatorOp::KeyOf => DesignType::Global("Object"),
+            },
+            TsType::TsLitType(l) => match &l.lit {
+                TsLit::Str(_) | TsLit::Tpl(_) => DesignType::Global("String"),
+                TsLit::Number(_) => DesignType::Global("Number"),
+                TsLit::Bool(_) => DesignType::Global("Boolean"),
+                TsLit::BigInt(_) => DesignType::Global("BigInt"),
+            },
+            TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(u)) => {
+                self.serialize_union(&u.types)
+            }
+            TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsIntersectionType(i)) => {
+                self.serialize_union(&i.types)
+            }
+            TsType::TsConditionalType(c) => {
+                self.serialize_union(&[c.true_type.clone(), c.false_type.clone()])
+            }
+            _ => DesignType::Global("Object"),
+        }
+    }
+
+    fn serialize_ann(&self, ann: Option<&TsTypeAnn>) -> DesignType {
+        ann.map(|a| self.serialize_type(&a.type_ann))
+            .unwrap_or(DesignType::Global("Object"))
+    }
+
+    fn param_types(&self, params: &[Param]) -> Vec<DesignType> {
+        params
+            .iter()
+            .filter(|p| p.pat.as_ident().is_none_or(|i| &*i.id.sym != "this"))
+            .map(|p| self.serialize_ann(pat_type_ann(&p.pat)))
+            .collect()
+    }
+
+    fn constructor_param_types(&self, params: &[ParamOrTsParamProp]) -> Vec<DesignType> {
+        params
+            .iter()
+            .map(|p| match p {
+                ParamOrTsParamProp::TsParamProp(p) => self.serialize_ann(match &p.param {
+                    TsParamPropParam::Ident(i) => i.type_ann.as_deref(),
+                    TsParamPropParam::Assign(a) => pat_type_ann(&a.left),
+                }),
+                ParamOrTsParamProp::Param(p) => self.serialize_ann(pat_type_ann(&p.pat)),
+            })
+            .collect()
+    }
+
+    fn return_type(&self, function: &Function) -> DesignType {
+        match function.return_type.as_deref() {
+            Some(ann) => self.serialize_type(&ann.type_ann),
+            None if function.is_async => DesignType::Global("Promise"),
+            None => DesignType::Void,
+        }
+    }
+
+    fn method_decorators(&self, function: &Function, kind: MethodKind) -> Vec<Decorator> {
+        let scope = self
+            .clone()
+            .with_type_params(function.type_params.as_deref());
+        match kind {
+            MethodKind::Method => vec![
+                type_decorator(DesignType::Global("Function")),
+                param_types_decorator(scope.param_types(&function.params)),
+                metadata_decorator("design:returntype", scope.return_type(function).into_expr()),
+            ],
+            MethodKind::Getter => vec![type_decorator(
+                scope.serialize_ann(function.return_type.as_deref()),
+            )],
+            MethodKind::Setter => {
+                let types = scope.param_types(&function.params);
+                vec![
+                    type_decorator(
+                        types
+                            .first()
+                            .cloned()
+                            .unwrap_or(DesignType::Global("Object")),
+                    ),
+                    param_types_decorator(types),
+                ]
+            }
+        }
+    }
+}
+
+fn typeof_undefined(expr: Box<Expr>) -> Box<Expr> {
+    Box::new(Expr::Bin(BinExpr {
+        span: DUMMY_SP,
+        op: BinaryOp::EqEqEq,
+        left: Box::new(Expr::Unary(UnaryExpr {
+            span: DUMMY_SP,
+            op: UnaryOp::TypeOf,
+            arg: expr,
+        })),
+        right: Box::new(Expr::Lit(Lit::Str(quote_str!("undefined")))),
+    }))
+}
+
+impl DesignType {
+    /// Converts into the runtime expression. References are guarded, as the
+    /// referenced entity may be a type only (ie: an interface): `Object` is used then.
+    fn into_expr(self) -> Box<Expr> {
+        match self {
+            DesignType::Void => undefined(DUMMY_SP),
+            DesignType::Global(name) => Box::new(Expr::Ident(ident(name))),
+            DesignType::Reference(path) => {
+                let mut expr: Option<Box<Expr>> = None;
+                let mut test: Option<Box<Expr>> = None;
+                for segment in path {
+                    let current = match expr {
+                        None => Box::new(Expr::Ident(segment)),
+                        Some(obj) => Box::new(Expr::Member(MemberExpr {
+                            span: DUMMY_SP,
+                            obj,
+                            prop: MemberProp::Ident(segment),
+                        })),
+                    };
+
+                    let is_undefined = typeof_undefined(current.clone());
+                    test = Some(match test {
+                        None => is_undefined,
+                        Some(test) => Box::new(Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            op: BinaryOp::LogicalOr,
+                            left: test,
+                            right: is_undefined,
+                        })),
+                    });
+                    expr = Some(current);
+                }
+
+                Box::new(Expr::Cond(CondExpr {
+                    span: DUMMY_SP,
+                    test: test.unwrap(),
+                    cons: Box::new(Expr::Ident(ident("Object"))),
+                    alt: expr.unwrap(),
+                }))
+            }
+        }
+    }
+}
+
+/// `__jymfony_metadata("design:...", () => value)`: the types are resolved lazily,
+/// as they may reference the decorated class or not yet initialized bindings.
+fn metadata_decorator(key: &str, value: Box<Expr>) -> Decorator {
+    let thunk = ArrowExpr {
+        span: DUMMY_SP,
+        params: vec![],
+        body: Box::new(BlockStmtOrExpr::Expr(value)),
+        is_async: false,
+        is_generator: false,
+        type_params: None,
+        return_type: None,
+    };
+
+    Decorator {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: ident("__jymfony_metadata").as_callee(),
+            args: vec![
+                Expr::Lit(Lit::Str(quote_str!(key))).as_arg(),
+                Expr::Arrow(thunk).as_arg(),
+            ],
+            type_args: None,
+        })),
+    }
+}
+
+fn type_decorator(ty: DesignType) -> Decorator {
+    metadata_decorator("design:type", ty.into_expr())
+}
+
+fn param_types_decorator(types: Vec<DesignType>) -> Decorator {
+    let types = ArrayLit {
+        span: DUMMY_SP,
+        elems: types
+            .into_iter()
+            .map(|t| Some(t.into_expr().as_arg()))
+            .collect(),
+    };
+
+    metadata_decorator("design:paramtypes", Box::new(Expr::Array(types)))
+}
+
+fn has_param_decorators(params: &[Param]) -> bool {
+    params.iter().any(|p| !p.decorators.is_empty())
+}
+
+impl VisitMut for DesignMetadata {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module(&mut self, n: &mut Module) {
+        let mut collector = EnumCollector::default();
+        n.visit_with(&mut collector);
+        self.enums = collector.enums;
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_script(&mut self, n: &mut Script) {
+        let mut collector = EnumCollector::default();
+        n.visit_with(&mut collector);
+        self.enums = collector.enums;
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_class(&mut self, n: &mut Class) {
+        n.visit_mut_children_with(self);
+
+        let scope = TypeScope::new(&self.enums, n.type_params.as_deref());
+
+        let constructor = n.body.iter().find_map(|m| m.as_constructor());
+        if let Some(constructor) = constructor {
+            let has_decorated_params = constructor.params.iter().any(|p| match p {
+                ParamOrTsParamProp::TsParamProp(p) => !p.decorators.is_empty(),
+                ParamOrTsParamProp::Param(p) => !p.decorators.is_empty(),
+            });
+
+            if !n.decorators.is_empty() || has_decorated_params {
+                let types = scope.constructor_param_types(&constructor.params);
+                n.decorators.push(param_types_decorator(types));
+            }
+        }
+
+        for member in n.body.iter_mut() {
+            match member {
+                ClassMember::Method(m)
+                    if !m.function.decorators.is_empty()
+                        || has_param_decorators(&m.function.params) =>
+                {
+                    let decorators = scope.method_decorators(&m.function, m.kind);
+                    m.function.decorators.extend(decorators);
+                }
+                ClassMember::PrivateMethod(m)
+                    if !m.function.decorators.is_empty()
+                        || has_param_decorators(&m.function.params) =>
+                {
+                    let decorators = scope.method_decorators(&m.function, m.kind);
+                    m.function.decorators.extend(decorators);
+                }
+                ClassMember::ClassProp(p) if !p.decorators.is_empty() => {
+                    let ty = scope.serialize_ann(p.type_ann.as_deref());
+                    p.decorators.push(type_decorator(ty));
+                }
+                ClassMember::PrivateProp(p) if !p.decorators.is_empty() => {
+                    let ty = scope.serialize_ann(p.type_ann.as_deref());
+                    p.decorators.push(type_decorator(ty));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{CodeParser, CompileOptions};
+    use crate::testing::uuid::reset_test_uuid;
+
+    fn compile(code: &str) -> anyhow::Result<String> {
+        reset_test_uuid();
+
+        let result = code
+            .parse_program(Some("service.ts"))?
+            .compile(CompileOptions {
+                as_module: true,
+                emit_decorator_metadata: true,
+                ..Default::default()
+            })?;
+
+        Ok(result.code)
+    }
+
+    #[test]
+    pub fn should_emit_design_types_of_decorated_members() -> anyhow::Result<()> {
+        let code = r#"
+import { Logger } from './logger';
+import type { Config } from './config';
+
+@injectable()
+export class Service {
+    constructor(private logger: Logger, config: Config, name: string | null) {}
+
+    @action()
+    async run(count: number, ...args: string[]): Promise<void> {}
+
+    @inject()
+    repo: ns.Repository;
+
+    undecorated(value: Logger) {}
+}
+"#;
+
+        let compiled = compile(code)?;
+        assert!(compiled.contains("import { Logger } from './logger';"));
+        assert!(!compiled.contains("./config"));
+        assert!(compiled.contains(r#"typeof Logger === "undefined" ? Object : Logger"#));
+        assert!(compiled.contains(r#"typeof Config === "undefined" ? Object : Config"#));
+        assert!(compiled.contains(r#"__jymfony_metadata("design:type", ()=>Function)"#));
+        assert!(compiled.contains(r#"__jymfony_metadata("design:returntype", ()=>typeof Promise === "undefined" ? Object : Promise)"#));
+        assert!(compiled.contains(
+            r#"typeof ns === "undefined" || typeof ns.Repository === "undefined" ? Object : ns.Repository"#
+        ));
+        assert_eq!(compiled.matches("__jymfony_metadata(").count(), 5);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_serialize_type_parameters_and_enums() -> anyhow::Result<()> {
+        let code = r#"
+enum Color { Red, Green }
+enum Mode { Read = 'r', Write = 'w' }
+
+export class Repository<T> {
+    @inject()
+    item: T;
+
+    @action()
+    find<K>(key: K, color: Color, mode: Mode.Read): T {}
+}
+"#;
+
+        let compiled = compile(code)?;
+        assert!(compiled.contains(r#"__jymfony_metadata("design:type", ()=>Object)"#));
+        assert!(compiled.contains(r#"__jymfony_metadata("design:returntype", ()=>Object)"#));
+        assert!(compiled.contains("Object,\n        Number,\n        String\n"));
+        assert!(!compiled.contains(r#"typeof T"#));
+        assert!(!compiled.contains(r#"typeof K"#));
+        assert!(!compiled.contains(r#"typeof Color"#));
+        assert!(!compiled.contains(r#"typeof Mode"#));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_not_emit_design_types_of_undecorated_classes() -> anyhow::Result<()> {
+        let compiled = compile(
+            r#"
+import { Logger } from './logger';
+
+export class Plain {
+    constructor(logger: Logger) {}
+    method(b: number): void {}
+}
+"#,
+        )?;
+
+        assert!(!compiled.contains("__jymfony_metadata"));
+        assert!(!compiled.contains("./logger"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,257 @@
+use super::class_jobject::JOBJECT_ACCESSOR;
+use crate::parser::util::ident;
+use crate::reflection::entity_expr_ident;
+use rustc_hash::FxHashSet;
+use std::iter::once;
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_utils::ExprFactory;
+use swc_ecma_visit::{
+    as_folder, noop_visit_mut_type, Fold, Visit, VisitMut, VisitMutWith, VisitWith,
+};
+
+/// Turns the `implements` clauses of TypeScript classes into Jymfony mixins:
+/// `class A extends B implements I` becomes `class A extends mix(B, I)`, hence
+/// `instanceof I` holds at runtime.
+/// Classes which do not extend another one are mixed into `__jymfony.JObject`, the base
+/// `class_jobject` would have given them.
+/// Only the interfaces bound to a value are applied: the ones declared with `getInterface`
+/// and the imported ones, which are then kept by `strip`. Local TypeScript interfaces and
+/// type-only imports are left to `strip`, which must run after.
+/// The compiled code expects `mix` to be defined globally, as `@jymfony/util` does.
+pub fn implements_mixins() -> impl VisitMut + Fold {
+    as_folder(ImplementsMixins::default())
+}
+
+#[derive(Default)]
+struct ImplementsMixins {
+    interfaces: FxHashSet<Id>,
+}
+
+/// Collects the bindings which may be interfaces at runtime: the variables initialized
+/// with a `getInterface(...)` call and the imports, except the type-only ones.
+#[derive(Default)]
+struct InterfaceCollector {
+    interfaces: FxHashSet<Id>,
+}
+
+impl Visit for InterfaceCollector {
+    fn visit_var_decl(&mut self, n: &VarDecl) {
+        if n.declare {
+            return;
+        }
+
+        for decl in &n.decls {
+            let Pat::Ident(name) = &decl.name else {
+                continue;
+            };
+
+            if decl.init.as_deref().is_some_and(is_get_interface_call) {
+                self.interfaces.insert(name.to_id());
+            }
+        }
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        if n.type_only {
+            return;
+        }
+
+        for specifier in &n.specifiers {
+            match specifier {
+                ImportSpecifier::Named(s) if s.is_type_only => {}
+                ImportSpecifier::Named(ImportNamedSpecifier { local, .. })
+                | ImportSpecifier::Default(ImportDefaultSpecifier { local, .. })
+                | ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => {
+                    self.interfaces.insert(local.to_id());
+                }
+            }
+        }
+    }
+}
+
+/// Whether the expression is a `getInterface(...)` call.
+fn is_get_interface_call(expr: &Expr) -> bool {
+    let Expr::Call(CallExpr {
+        callee: Callee::Expr(callee),
+        ..
+    }) = expr
+    else {
+        return false;
+    };
+
+    matches!(&**callee, Expr::Ident(i) if &*i.sym == "getInterface")
+}
+
+impl ImplementsMixins {
+    fn is_interface(&self, expr: &Expr) -> bool {
+        entity_expr_ident(expr).is_some_and(|i| self.interfaces.contains(&i.to_id()))
+    }
+}
+
+/// `super();`, required by the constructors of classes which did not extend another one.
+fn super_call() -> Stmt {
+    Stmt::Expr(ExprStmt {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: Callee::Super(Super { span: DUMMY_SP }),
+            args: vec![],
+            type_args: None,
+        })),
+    })
+}
+
+impl VisitMut for ImplementsMixins {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module(&mut self, n: &mut Module) {
+        let mut collector = InterfaceCollector::default();
+        n.visit_with(&mut collector);
+        self.interfaces = collector.interfaces;
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_script(&mut self, n: &mut Script) {
+        let mut collector = InterfaceCollector::default();
+        n.visit_with(&mut collector);
+        self.interfaces = collector.interfaces;
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_class(&mut self, n: &mut Class) {
+        n.visit_mut_children_with(self);
+
+        let (mixins, types): (Vec<_>, Vec<_>) =
+            n.implements.drain(..).partition(|i| self.is_interface(&i.expr));
+        n.implements = types;
+        if mixins.is_empty() {
+            return;
+        }
+
+        let is_derived = n.super_class.is_some();
+        let super_class = n
+            .super_class
+            .take()
+            .unwrap_or_else(|| Box::new(Expr::Member(JOBJECT_ACCESSOR.clone())));
+        n.super_class = Some(Box::new(Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: ident("mix").as_callee(),
+            args: once(super_class.as_arg())
+                .chain(mixins.into_iter().map(|i| i.expr.as_arg()))
+                .collect(),
+            type_args: None,
+        })));
+
+        if is_derived {
+            return;
+        }
+
+        for member in n.body.iter_mut() {
+            if let ClassMember::Constructor(Constructor {
+                body: Some(body), ..
+            }) = member
+            {
+                body.stmts.insert(0, super_call());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{CodeParser, CompileOptions};
+    use crate::reflection::get_reflection_data;
+    use crate::testing::uuid::reset_test_uuid;
+
+    #[test]
+    pub fn should_apply_implemented_interfaces_as_mixins() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let code = r#"
+import { Countable } from './countable';
+import type { Serializable } from './serializable';
+
+const Comparable = getInterface(class Comparable {});
+interface Local {}
+class Shape {}
+
+export class Base {}
+
+export class Collection extends Base implements Countable, Serializable, Comparable {}
+
+export class Square implements Shape {}
+
+export class Entity implements Local, Comparable {
+    constructor(private id: number) {
+        console.log(id);
+    }
+}
+"#;
+
+        let result = code
+            .parse_program(Some("mixins.ts"))?
+            .compile(CompileOptions {
+                as_module: true,
+                implements_mixins: true,
+                ..Default::default()
+            })?;
+
+        assert!(result
+            .code
+            .contains("import { Countable } from './countable';"));
+        assert!(!result.code.contains("./serializable"));
+        assert!(result.code.contains("mix(_Base, Countable, Comparable)"));
+        assert!(result.code.contains("mix(__jymfony.JObject, Comparable)"));
+        assert_eq!(result.code.matches("mix(").count(), 2);
+        assert!(result.code.contains("super();"));
+        assert!(!result.code.contains("implements"));
+
+        let implements = get_reflection_data(&result.class_ids[3])
+            .unwrap()
+            .implements
+            .iter()
+            .map(|i| (i.name.clone(), i.specifier.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            implements,
+            vec![
+                ("Countable".to_string(), Some("./countable".to_string())),
+                (
+                    "Serializable".to_string(),
+                    Some("./serializable".to_string())
+                ),
+                ("Comparable".to_string(), None),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn should_keep_implements_clauses_type_only_by_default() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let code = r#"
+const Comparable = getInterface(class Comparable {});
+export class Entity implements Comparable {}
+"#;
+
+        let result = code
+            .parse_program(Some("mixins.ts"))?
+            .compile(CompileOptions {
+                as_module: true,
+                ..Default::default()
+            })?;
+
+        assert!(!result.code.contains("mix("));
+        let data = get_reflection_data(&result.class_ids[1]).unwrap();
+        assert_eq!(data.implements[0].name, "Comparable");
+
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
 mod anonymous_expr;
+mod async_generators;
 mod class_jobject;
 mod class_reflection_decorators;
 mod decorator_2022_03;
+mod design_metadata;
+mod implements_mixins;
 mod lazy_object_construction;
 mod optional_import;
 mod remove_assert_calls;
@@ -9,12 +12,15 @@ mod resolve_self_identifiers;
 mod static_blocks;
 mod wrap_in_function;
 
-pub(crate) use anonymous_expr::anonymous_expr;
+pub(crate) use anonymous_expr::{anonymous_expr, ANONYMOUS_PREFIX};
+pub(crate) use async_generators::async_generators;
 pub(crate) use class_jobject::class_jobject;
-pub(crate) use class_reflection_decorators::class_reflection_decorators;
+pub(crate) use class_reflection_decorators::{class_reflection_decorators, CollectedClass};
 pub(crate) use decorator_2022_03::decorator_2022_03;
+pub(crate) use design_metadata::design_metadata;
+pub(crate) use implements_mixins::implements_mixins;
 pub(crate) use lazy_object_construction::lazy_object_construction;
-pub(crate) use optional_import::optional_import;
+pub(crate) use optional_import::{has_optional_attribute, optional_import};
 pub(crate) use remove_assert_calls::remove_assert_calls;
 pub(crate) use resolve_self_identifiers::resolve_self_identifiers;
 pub(crate) use static_blocks::static_blocks;
@@ -7,6 +7,23 @@ use swc_ecma_transforms_base::enable_helper;
 use swc_ecma_utils::{private_ident, quote_ident, undefined, ExprFactory};
 use swc_ecma_visit::{as_folder, noop_visit_mut_type, Fold, VisitMut, VisitMutWith};
 
+/// Whether the import attributes contain `optional: true`.
+pub(crate) fn has_optional_attribute(with: &ObjectLit) -> bool {
+    with.props.iter().any(|p| {
+        p.as_prop()
+            .and_then(|p| p.as_key_value())
+            .map(|kv| {
+                kv.key.as_ident().is_some_and(|i| i.sym == "optional")
+                    && kv.value.as_lit().is_some_and(|l| match l {
+                        Lit::Bool(b) => b.value,
+                        Lit::Str(s) => s.value == "true",
+                        _ => false,
+                    })
+            })
+            .unwrap_or(false)
+    })
+}
+
 pub fn optional_import(unresolved_mark: Mark) -> impl VisitMut + Fold {
     as_folder(OptionalImport {
         unresolved_mark,
@@ -82,21 +99,7 @@ impl VisitMut for OptionalImport {
             return;
         };
 
-        let optional = w.props.iter().find(|p| {
-            p.as_prop()
-                .and_then(|p| p.as_key_value())
-                .map(|kv| {
-                    kv.key.as_ident().is_some_and(|i| i.sym == "optional")
-                        && kv.value.as_lit().is_some_and(|l| match l {
-                            Lit::Bool(b) => b.value,
-                            Lit::Str(s) => s.value == "true",
-                            _ => false,
-                        })
-                })
-                .unwrap_or(false)
-        });
-
-        if optional.is_some() {
+        if has_optional_attribute(w) {
             self.optional_imports.push(n.take());
         }
     }
@@ -1,6 +1,64 @@
+use crate::parser::is_valid_identifier;
 use swc_common::DUMMY_SP;
 use swc_ecma_ast::*;
 
 pub(crate) fn ident(word: &str) -> Ident {
     Ident::new(word.into(), DUMMY_SP)
 }
+
+/// Converts a JSON value into the equivalent javascript literal.
+/// Null object properties are omitted, so that they read as `undefined`.
+pub(crate) fn json_to_expr(value: &serde_json::Value) -> Expr {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+        Value::Bool(value) => Expr::Lit(Lit::Bool(Bool {
+            span: DUMMY_SP,
+            value: *value,
+        })),
+        Value::Number(n) => Expr::Lit(Lit::Num(Number {
+            span: DUMMY_SP,
+            value: n.as_f64().unwrap_or_default(),
+            raw: None,
+        })),
+        Value::String(s) => Expr::Lit(Lit::Str(Str {
+            span: DUMMY_SP,
+            value: s.as_str().into(),
+            raw: None,
+        })),
+        Value::Array(values) => Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: values
+                .iter()
+                .map(|v| {
+                    Some(ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(json_to_expr(v)),
+                    })
+                })
+                .collect(),
+        }),
+        Value::Object(map) => Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: map
+                .iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| {
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: if is_valid_identifier(k) {
+                            PropName::Ident(ident(k))
+                        } else {
+                            PropName::Str(Str {
+                                span: DUMMY_SP,
+                                value: k.as_str().into(),
+                                raw: None,
+                            })
+                        },
+                        value: Box::new(json_to_expr(v)),
+                    })))
+                })
+                .collect(),
+        }),
+    }
+}
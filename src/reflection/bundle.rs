@@ -0,0 +1,119 @@
+use super::{get_reflection_data, restore_class, ReflectionData};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Version of the serialized format, bumped on incompatible changes of the reflection data.
+const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct BundleRef<'a> {
+    version: u32,
+    classes: Vec<(String, &'a ReflectionData)>,
+}
+
+#[derive(Deserialize)]
+struct Bundle {
+    version: u32,
+    classes: Vec<(String, ReflectionData)>,
+}
+
+/// Serializes the reflection data registered for the given classes, so that
+/// it can be shipped along with the compiled code and loaded back with
+/// [`import_reflection_data`].
+/// Classes which are not registered are skipped.
+pub fn export_reflection_data(class_ids: &[Uuid]) -> Result<Vec<u8>> {
+    let registered = class_ids
+        .iter()
+        .filter_map(|id| Some((id.to_string(), get_reflection_data(id)?)))
+        .collect::<Vec<_>>();
+
+    Ok(serde_json::to_vec(&BundleRef {
+        version: BUNDLE_VERSION,
+        classes: registered
+            .iter()
+            .map(|(id, data)| (id.clone(), data.as_ref()))
+            .collect(),
+    })?)
+}
+
+/// Registers the reflection data serialized by [`export_reflection_data`],
+/// replacing the data already registered under the same ids.
+/// Returns the ids of the imported classes.
+pub fn import_reflection_data(data: &[u8]) -> Result<Vec<Uuid>> {
+    let bundle: Bundle = serde_json::from_slice(data)?;
+    if bundle.version != BUNDLE_VERSION {
+        return Err(anyhow!(
+            "unsupported reflection data version {} (expected {})",
+            bundle.version,
+            BUNDLE_VERSION
+        ));
+    }
+
+    let classes = bundle
+        .classes
+        .into_iter()
+        .map(|(id, data)| Ok((Uuid::parse_str(&id)?, data)))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(classes
+        .into_iter()
+        .map(|(id, data)| {
+            restore_class(&id, Arc::new(data));
+            id
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_reflection_data, import_reflection_data};
+    use crate::parser::CodeParser;
+    use crate::reflection::{get_reflection_data, process_reflection_data};
+    use crate::testing::uuid::reset_test_uuid;
+    use uuid::Uuid;
+
+    #[test]
+    pub fn should_export_and_import_reflection_data() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let code = r#"
+/** Exported docblock */
+export default class Exported {
+    /** Method docblock */
+    method(a, b = 1) {}
+}
+"#;
+
+        let program = code.parse_program(Some("exported.js"))?;
+        let result = program.compile(Default::default())?;
+        let class_id = result.class_ids[0];
+        let unknown = Uuid::from_u128(42);
+
+        let exported = export_reflection_data(&[class_id, unknown])?;
+        let before = serde_json::to_value(process_reflection_data(
+            &get_reflection_data(&class_id).unwrap(),
+        ))?;
+
+        // Importing into another thread simulates a fresh registry.
+        let imported = std::thread::spawn(move || -> anyhow::Result<_> {
+            assert!(get_reflection_data(&class_id).is_none());
+
+            let ids = import_reflection_data(&exported)?;
+            let data = get_reflection_data(&class_id).unwrap();
+
+            Ok((ids, serde_json::to_value(process_reflection_data(&data))?))
+        })
+        .join()
+        .unwrap()?;
+
+        assert_eq!(imported.0, vec![class_id]);
+        assert_eq!(imported.1, before);
+
+        assert!(import_reflection_data(br#"{"version":0,"classes":[]}"#).is_err());
+        assert!(import_reflection_data(b"garbage").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+use crate::reflection::{
+    Docblock, JsTypeAnnotation, JsTypeReference, MemberAccessibility, MemberKey, MemberKind,
+    ReflectionData,
+};
+use serde::{Deserialize, Serialize};
+use swc_ecma_ast::*;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum Scalar {
+    Str(String),
+    Bool(bool),
+    Null,
+    Num(f64),
+    BigInt(String),
+    Regex { exp: String, flags: String },
+}
+
+impl TryFrom<&Lit> for Scalar {
+    type Error = ();
+
+    fn try_from(value: &Lit) -> Result<Self, Self::Error> {
+        match value {
+            Lit::Str(s) => Ok(Scalar::Str(s.value.to_string())),
+            Lit::Bool(b) => Ok(Scalar::Bool(b.value)),
+            Lit::Null(_) => Ok(Scalar::Null),
+            Lit::Num(n) => Ok(Scalar::Num(n.value)),
+            Lit::BigInt(n) => Ok(Scalar::BigInt(n.value.to_string())),
+            Lit::Regex(r) => Ok(Scalar::Regex {
+                exp: r.exp.to_string(),
+                flags: r.flags.to_string(),
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsMethodParameter {
+    pub name: Option<String>,
+    pub index: usize,
+    pub has_default: bool,
+    pub scalar_default: Option<Scalar>,
+    pub is_object_pattern: bool,
+    pub is_array_pattern: bool,
+    pub is_rest_element: bool,
+    /// The TypeScript type annotation of the parameter.
+    #[serde(rename = "type")]
+    pub type_: Option<JsTypeAnnotation>,
+    /// The accessibility of constructor parameter properties.
+    pub accessibility: Option<MemberAccessibility>,
+    pub is_readonly: bool,
+}
+
+impl From<&Param> for JsMethodParameter {
+    fn from(value: &Param) -> Self {
+        let (is_rest, pat) = if let Pat::Rest(r) = &value.pat {
+            (true, r.arg.clone())
+        } else {
+            (false, Box::new(value.pat.clone()))
+        };
+
+        let (ident, def) = if let Pat::Assign(a) = pat.as_ref() {
+            let def = a.right.as_lit().and_then(|l| Scalar::try_from(l).ok());
+            (a.left.as_ident(), def)
+        } else {
+            (pat.as_ident(), None)
+        };
+
+        JsMethodParameter {
+            name: ident.map(|i| i.sym.to_string()),
+            index: 0,
+            has_default: pat.is_assign(),
+            scalar_default: def,
+            is_object_pattern: pat.is_object(),
+            is_array_pattern: pat.is_array(),
+            is_rest_element: is_rest,
+            type_: None,
+            accessibility: None,
+            is_readonly: false,
+        }
+    }
+}
+
+/// The descriptor of a class member.
+#[derive(Serialize, Deserialize)]
+pub struct JsMemberData {
+    /// The index of the member in the class body.
+    pub index: usize,
+    pub kind: MemberKind,
+    /// The key of the member, absent if computed by an expression
+    /// which cannot be evaluated at compile time.
+    pub key: Option<MemberKey>,
+    #[serde(rename = "static")]
+    pub is_static: bool,
+    #[serde(rename = "private")]
+    pub is_private: bool,
+    #[serde(rename = "computed")]
+    pub is_computed: bool,
+    /// The parameters of constructors and methods.
+    pub params: Option<Vec<JsMethodParameter>>,
+    /// The TypeScript type annotation of fields.
+    #[serde(rename = "type")]
+    pub type_: Option<JsTypeAnnotation>,
+    /// The TypeScript return type of methods and getters.
+    #[serde(rename = "returnType")]
+    pub return_type: Option<JsTypeAnnotation>,
+    pub accessibility: Option<MemberAccessibility>,
+    #[serde(rename = "readonly")]
+    pub is_readonly: bool,
+    pub docblock: Option<String>,
+    #[serde(rename = "parsedDocblock")]
+    pub parsed_docblock: Option<Docblock>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsReflectionData {
+    pub fqcn: String,
+    pub class_name: String,
+    pub namespace: Option<String>,
+    pub filename: Option<String>,
+    pub members: Vec<JsMemberData>,
+    pub implements: Vec<JsTypeReference>,
+    pub docblock: Option<String>,
+    #[serde(rename = "parsedDocblock")]
+    pub parsed_docblock: Option<Docblock>,
+}
+
+/// Converts the reflection data collected at compile time into the
+/// serializable representation exposed to the javascript runtime.
+pub fn process_reflection_data(reflection_data: &ReflectionData) -> JsReflectionData {
+    let namespace = reflection_data.namespace.clone();
+
+    let members = reflection_data
+        .members
+        .iter()
+        .map(|m| JsMemberData {
+            index: m.index,
+            kind: m.kind,
+            key: m.key.clone(),
+            is_static: m.is_static,
+            is_private: m.is_private,
+            is_computed: m.is_computed,
+            params: match m.kind {
+                MemberKind::Field | MemberKind::Accessor => None,
+                _ => Some(m.params.clone()),
+            },
+            type_: m.type_.clone(),
+            return_type: m.return_type.clone(),
+            accessibility: m.accessibility,
+            is_readonly: m.is_readonly,
+            docblock: m.docblock.clone(),
+            parsed_docblock: m.docblock.as_deref().map(Docblock::parse),
+        })
+        .collect();
+
+    let class_name = reflection_data.name.clone();
+    let ns = namespace.as_deref();
+    let fqcn = if ns.is_some_and(|n| !n.is_empty()) {
+        format!("{}.{}", ns.unwrap(), class_name)
+    } else {
+        class_name.clone()
+    };
+
+    JsReflectionData {
+        fqcn,
+        class_name,
+        namespace,
+        filename: reflection_data.filename.clone(),
+        members,
+        implements: reflection_data.implements.clone(),
+        docblock: reflection_data.docblock.clone(),
+        parsed_docblock: reflection_data.docblock.as_deref().map(Docblock::parse),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::CodeParser;
+    use crate::reflection::data::process_reflection_data;
+    use crate::reflection::ReflectionData;
+    use crate::reflection::{MemberKey, MemberKind};
+    use swc_common::DUMMY_SP;
+    use swc_ecma_ast::Ident;
+
+    #[test]
+    pub fn should_process_method_parameters_correctly() -> anyhow::Result<()> {
+        let code = r#"
+/** class docblock */
+export default class x {
+    static #staticPrivateField;
+    #privateField;
+    accessor #privateAccessor;
+    static staticPublicField;
+    publicField;
+    accessor publicAccessor;
+
+    /** constructor docblock */
+    constructor(@type(String) constructorParam1) {
+    }
+
+    /**
+     * computed method docblock
+     */
+    [a()]() {}
+    #privateMethod(a, b = 1, [c, d], {f, g}) {}
+
+    /**
+     * public method docblock
+     */
+    publicMethod({a, b} = {}, c = new Object(), ...x) {}
+    static #staticPrivateMethod() {}
+    static staticPublicMethod() {}
+
+    get [a()]() {}
+    set b(v) {}
+
+    get #ap() {}
+    set #bp(v) {}
+
+    act(@type(String) param1) {}
+    [a()](@type(String) param1) {}
+    [Symbol.for('xtest')](@type(String) param1) {}
+}
+
+return x[Symbol.metadata].act[Symbol.parameters][0].type;
+"#;
+
+        let program = code.parse_program(None)?;
+        let mut module = program.program.expect_module();
+        let body = module.body.drain(..);
+        let item = body.take(1).into_iter().nth(0).unwrap();
+        let class_decl = item
+            .expect_module_decl()
+            .expect_export_default_decl()
+            .decl
+            .expect_class();
+
+        let name = Ident {
+            span: DUMMY_SP,
+            sym: "x".into(),
+            optional: false,
+        };
+        let data = process_reflection_data(&ReflectionData::new(
+            &class_decl.class,
+            &name,
+            None,
+            None,
+            Default::default(),
+            &Default::default(),
+        ));
+
+        let method = &data.members[8];
+        assert_eq!(method.kind, MemberKind::Method);
+        let params = method.params.as_deref().unwrap();
+        assert!(params[0].name.is_some());
+        assert!(params[1].name.is_some());
+
+        let computed = &data.members[7];
+        assert!(computed.is_computed);
+        assert!(computed.key.is_none());
+
+        let getter = &data.members[14];
+        assert_eq!(getter.kind, MemberKind::Getter);
+        assert!(getter.is_private);
+        assert_eq!(getter.key, Some(MemberKey::String("#ap".to_string())));
+
+        let symbol = &data.members[18];
+        assert_eq!(symbol.key, Some(MemberKey::Symbol("xtest".to_string())));
+        assert!(data.members[0].params.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+
+/// The tags whose first word, after the type, is a name.
+const NAMED_TAGS: [&str; 9] = [
+    "param",
+    "arg",
+    "argument",
+    "property",
+    "prop",
+    "memberOf",
+    "memberof",
+    "namespace",
+    "template",
+];
+
+/// A tag of a docblock, ie: `@param {string} name The description`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocblockTag {
+    /// The tag name, without the leading `@`.
+    pub tag: String,
+    /// The type between braces, if any.
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    /// The name documented by the tag (parameters, properties and namespaces).
+    /// Brackets and defaults of optional parameters are removed.
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+/// A docblock parsed into its summary, description and tags.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Docblock {
+    /// The first paragraph of the text.
+    pub summary: Option<String>,
+    /// The text following the summary, up to the first tag.
+    pub description: Option<String>,
+    pub tags: Vec<DocblockTag>,
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Splits a leading `{type}` off the text, braces can be nested.
+fn split_type(text: &str) -> (Option<String>, &str) {
+    if !text.starts_with('{') {
+        return (None, text);
+    }
+
+    let mut depth = 0;
+    for (idx, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return (non_empty(&text[1..idx]), text[idx + 1..].trim_start());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    (None, text)
+}
+
+/// Splits the documented name off the text: `name`, `[name]` or `[name=default]`.
+fn split_name(text: &str) -> (Option<String>, &str) {
+    let (name, rest) = if let Some(optional) = text.strip_prefix('[') {
+        match optional.find(']') {
+            Some(end) => (&optional[..end], &optional[end + 1..]),
+            None => (optional, ""),
+        }
+    } else {
+        text.split_once(char::is_whitespace).unwrap_or((text, ""))
+    };
+
+    let name = name.split('=').next().unwrap_or_default();
+    (non_empty(name), rest.trim_start())
+}
+
+impl DocblockTag {
+    fn parse(text: &str) -> Self {
+        let (tag, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let (type_, rest) = split_type(rest.trim_start());
+        let (name, rest) = if NAMED_TAGS.contains(&tag) {
+            split_name(rest)
+        } else {
+            (None, rest)
+        };
+
+        let rest = rest.trim_start();
+        let description = rest.strip_prefix("- ").unwrap_or(rest);
+
+        Self {
+            tag: tag.to_string(),
+            type_,
+            name,
+            description: non_empty(description),
+        }
+    }
+}
+
+impl Docblock {
+    /// Parses the text of a `/** ... */` comment.
+    pub fn parse(docblock: &str) -> Self {
+        let content = docblock.trim();
+        let content = content.strip_prefix("/**").unwrap_or(content);
+        let content = content.strip_suffix("*/").unwrap_or(content);
+
+        let lines = content.lines().map(|line| {
+            let line = line.trim_start();
+            let line = line.strip_prefix('*').unwrap_or(line);
+            line.strip_prefix(' ').unwrap_or(line).trim_end()
+        });
+
+        let mut text = vec![];
+        let mut tags: Vec<String> = vec![];
+        for line in lines {
+            if let Some(tag) = line.strip_prefix('@') {
+                tags.push(tag.to_string());
+            } else if let Some(tag) = tags.last_mut() {
+                tag.push('\n');
+                tag.push_str(line);
+            } else {
+                text.push(line);
+            }
+        }
+
+        let text = text.join("\n");
+        let text = text.trim();
+        let (summary, description) = text.split_once("\n\n").unwrap_or((text, ""));
+
+        Self {
+            summary: non_empty(&summary.lines().map(str::trim).collect::<Vec<_>>().join(" ")),
+            description: non_empty(description),
+            tags: tags.iter().map(|t| DocblockTag::parse(t)).collect(),
+        }
+    }
+
+    /// Returns the tags with the given name.
+    pub fn get_tags<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a DocblockTag> + 'a {
+        self.tags.iter().filter(move |t| t.tag == tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Docblock, DocblockTag};
+
+    #[test]
+    pub fn should_parse_docblocks() {
+        let docblock = Docblock::parse(
+            r#"/**
+     * Computes the total
+     * of an order.
+     *
+     * The taxes are included.
+     * Discounts are not.
+     *
+     * @param {Map<string, {price: number}>} items - The items
+     * @param {number} [discount=0] The discount,
+     *     in percentage
+     * @returns {Promise<number>}
+     * @type {Function}
+     * @deprecated Use totalWithDiscounts instead.
+     * @internal
+     * @memberOf App.Order
+     * @inject logger
+     */"#,
+        );
+
+        assert_eq!(
+            docblock.summary.as_deref(),
+            Some("Computes the total of an order.")
+        );
+        assert_eq!(
+            docblock.description.as_deref(),
+            Some("The taxes are included.\nDiscounts are not.")
+        );
+
+        let tag =
+            |tag: &str, type_: Option<&str>, name: Option<&str>, desc: Option<&str>| DocblockTag {
+                tag: tag.to_string(),
+                type_: type_.map(ToString::to_string),
+                name: name.map(ToString::to_string),
+                description: desc.map(ToString::to_string),
+            };
+
+        assert_eq!(
+            docblock.tags,
+            vec![
+                tag(
+                    "param",
+                    Some("Map<string, {price: number}>"),
+                    Some("items"),
+                    Some("The items")
+                ),
+                tag(
+                    "param",
+                    Some("number"),
+                    Some("discount"),
+                    Some("The discount,\n    in percentage")
+                ),
+                tag("returns", Some("Promise<number>"), None, None),
+                tag("type", Some("Function"), None, None),
+                tag(
+                    "deprecated",
+                    None,
+                    None,
+                    Some("Use totalWithDiscounts instead.")
+                ),
+                tag("internal", None, None, None),
+                tag("memberOf", None, Some("App.Order"), None),
+                tag("inject", None, None, Some("logger")),
+            ]
+        );
+
+        assert_eq!(docblock.get_tags("param").count(), 2);
+    }
+
+    #[test]
+    pub fn should_parse_single_line_docblocks() {
+        let docblock = Docblock::parse("/** @type {string} */");
+        assert_eq!(docblock.summary, None);
+        assert_eq!(docblock.tags.len(), 1);
+        assert_eq!(docblock.tags[0].type_.as_deref(), Some("string"));
+
+        let docblock = Docblock::parse("/** Just a summary */");
+        assert_eq!(docblock.summary.as_deref(), Some("Just a summary"));
+        assert!(docblock.tags.is_empty());
+    }
+}
@@ -0,0 +1,398 @@
+use super::types::pat_type_ann;
+use super::{Imports, JsMethodParameter, JsTypeAnnotation, MemberAccessibility, Scalar};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use swc_common::{Span, Spanned};
+use swc_ecma_ast::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemberKind {
+    Constructor,
+    Method,
+    Getter,
+    Setter,
+    Field,
+    Accessor,
+}
+
+/// The key of a class member.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum MemberKey {
+    /// A string key; private names are prefixed by `#`.
+    String(String),
+    /// A symbol key, by description (ie: `Symbol.iterator` or `x` for `Symbol.for('x')`).
+    Symbol(String),
+}
+
+/// The reflection data of a class member, collected at compile time.
+#[derive(Serialize, Deserialize)]
+pub struct MemberData {
+    /// The index of the member in the class body.
+    pub index: usize,
+    pub kind: MemberKind,
+    /// The key of the member, `None` if it is computed and cannot be evaluated statically.
+    pub key: Option<MemberKey>,
+    pub is_computed: bool,
+    pub is_static: bool,
+    pub is_private: bool,
+    /// The parameters of constructors and methods.
+    pub params: Vec<JsMethodParameter>,
+    /// The TypeScript type annotation of fields.
+    pub type_: Option<JsTypeAnnotation>,
+    /// The TypeScript return type of methods and getters.
+    pub return_type: Option<JsTypeAnnotation>,
+    pub accessibility: Option<MemberAccessibility>,
+    pub is_readonly: bool,
+    pub docblock: Option<String>,
+}
+
+/// Evaluates a computed key, if it is a literal or a symbol.
+fn computed_key(expr: &Expr) -> Option<MemberKey> {
+    match expr {
+        Expr::Paren(p) => computed_key(&p.expr),
+        Expr::Lit(Lit::Str(s)) => Some(MemberKey::String(s.value.to_string())),
+        Expr::Lit(Lit::Num(n)) => Some(MemberKey::String(n.value.to_string())),
+        Expr::Tpl(t) if t.exprs.is_empty() => t
+            .quasis
+            .first()
+            .and_then(|q| q.cooked.as_ref())
+            .map(|c| MemberKey::String(c.to_string())),
+        // Well-known symbols: `Symbol.iterator`
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if obj.as_ident().is_some_and(|o| &*o.sym == "Symbol") => {
+            Some(MemberKey::Symbol(format!("Symbol.{}", prop.sym)))
+        }
+        // Registered symbols: `Symbol.for('key')`
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            args,
+            ..
+        }) => {
+            let Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) = callee.as_ref()
+            else {
+                return None;
+            };
+
+            if obj.as_ident().is_none_or(|o| &*o.sym != "Symbol") || &*prop.sym != "for" {
+                return None;
+            }
+
+            match args.first().map(|a| (a.spread, a.expr.as_ref())) {
+                Some((None, Expr::Lit(Lit::Str(s)))) => {
+                    Some(MemberKey::Symbol(s.value.to_string()))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The key of a member and whether it is computed.
+fn prop_key(key: &PropName) -> (Option<MemberKey>, bool) {
+    let key = match key {
+        PropName::Ident(i) => i.sym.to_string(),
+        PropName::Str(s) => s.value.to_string(),
+        PropName::Num(n) => n.value.to_string(),
+        PropName::BigInt(b) => b.value.to_string(),
+        PropName::Computed(c) => return (computed_key(&c.expr), true),
+    };
+
+    (Some(MemberKey::String(key)), false)
+}
+
+fn private_key(name: &PrivateName) -> (Option<MemberKey>, bool) {
+    (Some(MemberKey::String(format!("#{}", name.id.sym))), false)
+}
+
+fn method_kind(kind: MethodKind) -> MemberKind {
+    match kind {
+        MethodKind::Method => MemberKind::Method,
+        MethodKind::Getter => MemberKind::Getter,
+        MethodKind::Setter => MemberKind::Setter,
+    }
+}
+
+fn params(params: &[Param], imports: &Imports) -> Vec<JsMethodParameter> {
+    params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let mut p = JsMethodParameter::from(param);
+            p.index = i;
+            p.type_ = JsTypeAnnotation::from_ann(pat_type_ann(&param.pat), imports);
+
+            p
+        })
+        .collect()
+}
+
+fn constructor_params(params: &[ParamOrTsParamProp], imports: &Imports) -> Vec<JsMethodParameter> {
+    params
+        .iter()
+        .enumerate()
+        .map(|(i, p)| match p {
+            ParamOrTsParamProp::TsParamProp(tp) => JsMethodParameter {
+                name: tp.param.as_ident().map(|i| i.sym.to_string()),
+                index: i,
+                has_default: tp.param.is_assign(),
+                scalar_default: tp
+                    .param
+                    .as_assign()
+                    .and_then(|a| a.right.as_lit())
+                    .and_then(|l| Scalar::try_from(l).ok()),
+                is_object_pattern: tp
+                    .param
+                    .as_assign()
+                    .map(|a| a.left.is_object())
+                    .unwrap_or(false),
+                is_array_pattern: tp
+                    .param
+                    .as_assign()
+                    .map(|a| a.left.is_array())
+                    .unwrap_or(false),
+                is_rest_element: false,
+                type_: JsTypeAnnotation::from_ann(
+                    match &tp.param {
+                        TsParamPropParam::Ident(i) => i.type_ann.as_deref(),
+                        TsParamPropParam::Assign(a) => pat_type_ann(&a.left),
+                    },
+                    imports,
+                ),
+                accessibility: tp.accessibility.map(MemberAccessibility::from),
+                is_readonly: tp.readonly,
+            },
+            ParamOrTsParamProp::Param(param) => {
+                let mut p = JsMethodParameter::from(param);
+                p.index = i;
+                p.type_ = JsTypeAnnotation::from_ann(pat_type_ann(&param.pat), imports);
+
+                p
+            }
+        })
+        .collect()
+}
+
+/// The TypeScript annotations and modifiers of a member.
+#[derive(Default)]
+struct MemberTypes {
+    type_: Option<JsTypeAnnotation>,
+    return_type: Option<JsTypeAnnotation>,
+    accessibility: Option<Accessibility>,
+    is_readonly: bool,
+}
+
+impl MemberTypes {
+    fn method(
+        function: &Function,
+        accessibility: Option<Accessibility>,
+        imports: &Imports,
+    ) -> Self {
+        Self {
+            return_type: JsTypeAnnotation::from_ann(function.return_type.as_deref(), imports),
+            accessibility,
+            ..Default::default()
+        }
+    }
+
+    fn field(
+        type_ann: Option<&TsTypeAnn>,
+        accessibility: Option<Accessibility>,
+        is_readonly: bool,
+        imports: &Imports,
+    ) -> Self {
+        Self {
+            type_: JsTypeAnnotation::from_ann(type_ann, imports),
+            accessibility,
+            is_readonly,
+            ..Default::default()
+        }
+    }
+}
+
+/// Collects the reflection data of the members of a class.
+/// Only the members which can be decorated are collected.
+pub(super) fn collect_members(
+    class: &Class,
+    docblock: &FxHashMap<Span, Option<String>>,
+    imports: &Imports,
+) -> Vec<MemberData> {
+    class
+        .body
+        .iter()
+        .enumerate()
+        .filter_map(|(index, n)| {
+            let (kind, (key, is_computed), is_static, is_private, params, types) = match n {
+                ClassMember::Constructor(c) => (
+                    MemberKind::Constructor,
+                    (Some(MemberKey::String("constructor".to_string())), false),
+                    false,
+                    false,
+                    constructor_params(&c.params, imports),
+                    MemberTypes {
+                        accessibility: c.accessibility,
+                        ..Default::default()
+                    },
+                ),
+                ClassMember::Method(m) => (
+                    method_kind(m.kind),
+                    prop_key(&m.key),
+                    m.is_static,
+                    false,
+                    params(&m.function.params, imports),
+                    MemberTypes::method(&m.function, m.accessibility, imports),
+                ),
+                ClassMember::PrivateMethod(m) => (
+                    method_kind(m.kind),
+                    private_key(&m.key),
+                    m.is_static,
+                    true,
+                    params(&m.function.params, imports),
+                    MemberTypes::method(&m.function, m.accessibility, imports),
+                ),
+                ClassMember::ClassProp(p) => (
+                    MemberKind::Field,
+                    prop_key(&p.key),
+                    p.is_static,
+                    false,
+                    vec![],
+                    MemberTypes::field(p.type_ann.as_deref(), p.accessibility, p.readonly, imports),
+                ),
+                ClassMember::PrivateProp(p) => (
+                    MemberKind::Field,
+                    private_key(&p.key),
+                    p.is_static,
+                    true,
+                    vec![],
+                    MemberTypes::field(p.type_ann.as_deref(), p.accessibility, p.readonly, imports),
+                ),
+                ClassMember::AutoAccessor(a) => {
+                    let (key, is_private) = match &a.key {
+                        Key::Private(p) => (private_key(p), true),
+                        Key::Public(key) => (prop_key(key), false),
+                    };
+
+                    (
+                        MemberKind::Accessor,
+                        key,
+                        a.is_static,
+                        is_private,
+                        vec![],
+                        MemberTypes::field(a.type_ann.as_deref(), a.accessibility, false, imports),
+                    )
+                }
+                _ => return None,
+            };
+
+            Some(MemberData {
+                index,
+                kind,
+                key,
+                is_computed,
+                is_static,
+                is_private,
+                params,
+                type_: types.type_,
+                return_type: types.return_type,
+                accessibility: types.accessibility.map(MemberAccessibility::from),
+                is_readonly: types.is_readonly,
+                docblock: docblock.get(&n.span()).cloned().unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::CodeParser;
+    use crate::reflection::{get_reflection_data, MemberKey, MemberKind};
+    use crate::testing::uuid::reset_test_uuid;
+
+    #[test]
+    pub fn should_collect_member_data() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let code = r#"
+export default class Members {
+    static #count = 0;
+    accessor value;
+
+    /** constructor docblock */
+    constructor(a, b = 'x') {}
+
+    static create() {}
+    get #secret() {}
+    set ['computed' + 1](v) {}
+    [Symbol.iterator]() {}
+    [Symbol.for('registered')] = 1;
+    ['literal']() {}
+    static {}
+}
+"#;
+
+        let result = code.parse_program(None)?.compile(Default::default())?;
+        let data = get_reflection_data(&result.class_ids[0]).unwrap();
+        assert_eq!(data.name, "Members");
+
+        let members = data
+            .members
+            .iter()
+            .map(|m| (m.index, m.kind, m.is_static, m.is_private))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            members,
+            vec![
+                (0, MemberKind::Field, true, true),
+                (1, MemberKind::Accessor, false, false),
+                (2, MemberKind::Constructor, false, false),
+                (3, MemberKind::Method, true, false),
+                (4, MemberKind::Getter, false, true),
+                (5, MemberKind::Setter, false, false),
+                (6, MemberKind::Method, false, false),
+                (7, MemberKind::Field, false, false),
+                (8, MemberKind::Method, false, false),
+            ]
+        );
+
+        let keys = data
+            .members
+            .iter()
+            .map(|m| (m.key.clone(), m.is_computed))
+            .collect::<Vec<_>>();
+        let string = |s: &str| Some(MemberKey::String(s.to_string()));
+        let symbol = |s: &str| Some(MemberKey::Symbol(s.to_string()));
+        assert_eq!(
+            keys,
+            vec![
+                (string("#count"), false),
+                (string("value"), false),
+                (string("constructor"), false),
+                (string("create"), false),
+                (string("#secret"), false),
+                (None, true),
+                (symbol("Symbol.iterator"), true),
+                (symbol("registered"), true),
+                (string("literal"), true),
+            ]
+        );
+
+        let constructor = &data.members[2];
+        assert_eq!(
+            constructor.docblock.as_deref(),
+            Some("/** constructor docblock */")
+        );
+        assert_eq!(constructor.params.len(), 2);
+        assert!(constructor.params[1].has_default);
+
+        Ok(())
+    }
+}
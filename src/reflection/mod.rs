@@ -1,82 +1,197 @@
+//! Registry of the classes compiled with reflection metadata.
+//!
+//! Every class processed by the compiler is registered under the UUID passed
+//! to its `__jymfony_reflect` decorator; the runtime looks it up by that id.
+//! The registered data can be exported and imported back, to ship the reflection
+//! metadata of precompiled code.
+
+mod bundle;
+mod data;
+mod docblock;
+mod member;
+mod types;
+
+use crate::stack::{register_source_map, unregister_source_map};
+pub use bundle::{export_reflection_data, import_reflection_data};
+pub use data::{
+    process_reflection_data, JsMemberData, JsMethodParameter, JsReflectionData, Scalar,
+};
+pub use docblock::{Docblock, DocblockTag};
 #[cfg(not(test))]
 use lazy_static::lazy_static;
+use member::collect_members;
+pub use member::{MemberData, MemberKey, MemberKind};
 use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
 use std::sync::{Arc, RwLock};
 use swc_common::Span;
 use swc_ecma_ast::{Class, Ident};
+use types::implemented_interfaces;
+pub(crate) use types::{collect_imports, entity_expr_ident, pat_type_ann};
+pub use types::{Imports, JsTypeAnnotation, JsTypeReference, MemberAccessibility};
 use uuid::Uuid;
 
 #[cfg(test)]
 thread_local! {
-    static CLASS_REGISTRY: RwLock<FxHashMap<Uuid, Arc<ReflectionData>>> = RwLock::new(Default::default());
+    static CLASS_REGISTRY: RwLock<Registry> = RwLock::new(Default::default());
 }
 
 #[cfg(not(test))]
 lazy_static! {
-    static ref CLASS_REGISTRY: RwLock<FxHashMap<Uuid, Arc<ReflectionData>>> =
-        RwLock::new(Default::default());
+    static ref CLASS_REGISTRY: RwLock<Registry> = RwLock::new(Default::default());
+}
+
+/// The registered classes, indexed by the file they have been compiled from,
+/// hence the classes of a file are replaced without scanning the whole registry.
+#[derive(Default)]
+struct Registry {
+    classes: FxHashMap<Uuid, Arc<ReflectionData>>,
+    files: FxHashMap<String, Vec<Uuid>>,
+}
+
+impl Registry {
+    fn insert(&mut self, class_id: Uuid, data: Arc<ReflectionData>) {
+        if let Some(filename) = &data.filename {
+            self.files
+                .entry(filename.clone())
+                .or_default()
+                .push(class_id);
+        }
+
+        if let Some(previous) = self.classes.insert(class_id, data) {
+            self.unindex(&class_id, &previous);
+        }
+    }
+
+    fn remove(&mut self, class_id: &Uuid) -> Option<Arc<ReflectionData>> {
+        let data = self.classes.remove(class_id)?;
+        self.unindex(class_id, &data);
+
+        Some(data)
+    }
+
+    /// Removes the classes compiled from the given file, returning their ids.
+    fn remove_file(&mut self, filename: &str) -> Vec<Uuid> {
+        let class_ids = self.files.remove(filename).unwrap_or_default();
+        for id in &class_ids {
+            self.classes.remove(id);
+        }
+
+        class_ids
+    }
+
+    fn unindex(&mut self, class_id: &Uuid, data: &ReflectionData) {
+        let Some(filename) = &data.filename else {
+            return;
+        };
+
+        if let Some(ids) = self.files.get_mut(filename) {
+            // A replaced class has already been indexed again: only its first entry is removed.
+            if let Some(idx) = ids.iter().position(|id| id == class_id) {
+                ids.remove(idx);
+            }
+
+            if ids.is_empty() {
+                self.files.remove(filename);
+            }
+        }
+    }
 }
 
+/// The reflection data of a compiled class.
+/// Only the data exposed to the runtime is kept, the class AST is not retained.
+#[derive(Serialize, Deserialize)]
 pub struct ReflectionData {
-    pub class: Class,
-    pub name: Ident,
+    pub name: String,
     pub filename: Option<String>,
     pub namespace: Option<String>,
-    pub docblock: FxHashMap<Span, Option<String>>,
+    pub docblock: Option<String>,
+    pub members: Vec<MemberData>,
+    /// The interfaces listed by the TypeScript `implements` clause.
+    pub implements: Vec<JsTypeReference>,
 }
 
 impl ReflectionData {
+    /// Collects the reflection data of a class.
+    /// `docblock` maps the spans of the class and its members to their docblock,
+    /// `imports` resolves the types referenced by the TypeScript annotations.
     pub fn new(
         class: &Class,
-        name: Ident,
+        name: &Ident,
         filename: Option<&str>,
         namespace: Option<&str>,
         docblock: FxHashMap<Span, Option<String>>,
+        imports: &Imports,
     ) -> Self {
         Self {
-            class: class.clone(),
-            name,
+            name: name.sym.to_string(),
             filename: filename.map(|s| s.to_string()),
             namespace: namespace.map(|s| s.to_string()),
-            docblock,
+            docblock: docblock.get(&class.span).cloned().unwrap_or_default(),
+            members: collect_members(class, &docblock, imports),
+            implements: implemented_interfaces(class, imports),
         }
     }
 }
 
-pub(crate) fn register_class(class_id: &Uuid, data: ReflectionData) {
+fn with_registry<R>(f: impl FnOnce(&RwLock<Registry>) -> R) -> R {
     #[cfg(not(test))]
-    {
-        let mut registry = CLASS_REGISTRY.write().unwrap();
-
-        debug_assert!(registry.get(class_id).is_none());
-        registry.insert(*class_id, Arc::new(data));
-    }
+    return f(&CLASS_REGISTRY);
 
     #[cfg(test)]
-    {
-        CLASS_REGISTRY.with(|lock| {
-            let mut registry = lock.write().unwrap();
+    return CLASS_REGISTRY.with(f);
+}
 
-            debug_assert!(registry.get(class_id).is_none());
-            registry.insert(*class_id, Arc::new(data));
-        });
-    }
+/// Registers previously collected reflection data (ie: restored from a cache),
+/// replacing the data already registered under the same id.
+pub(crate) fn restore_class(class_id: &Uuid, data: Arc<ReflectionData>) {
+    with_registry(|lock| {
+        let mut registry = lock.write().unwrap();
+        registry.insert(*class_id, data);
+    });
 }
 
-pub(crate) fn get_reflection_data(class_id: &Uuid) -> Option<Arc<ReflectionData>> {
-    #[cfg(not(test))]
-    let data = {
-        let registry = CLASS_REGISTRY.read().unwrap();
-        registry.get(class_id).map(Clone::clone)
-    };
+/// Registers the classes and the source map of a compiled file.
+/// The classes and the source map previously registered for the same file are
+/// replaced while holding the registry lock, hence a recompiled file is never
+/// observed partially registered.
+pub(crate) fn register_file(
+    filename: Option<&str>,
+    classes: Vec<(Uuid, Arc<ReflectionData>)>,
+    source_map: Option<sourcemap::SourceMap>,
+) {
+    with_registry(|lock| {
+        let mut registry = lock.write().unwrap();
+        if let Some(filename) = filename {
+            registry.remove_file(filename);
+            match source_map {
+                Some(source_map) => register_source_map(filename.to_string(), source_map),
+                None => {
+                    unregister_source_map(filename);
+                }
+            }
+        }
 
-    #[cfg(test)]
-    let data = {
-        CLASS_REGISTRY.with(|lock| {
-            let registry = lock.read().unwrap();
-            registry.get(class_id).map(Clone::clone)
-        })
-    };
-
-    data
+        for (class_id, data) in classes {
+            registry.insert(class_id, data);
+        }
+    });
+}
+
+/// Removes the reflection data of a class.
+/// Returns whether the class was registered.
+pub fn unregister_class(class_id: &Uuid) -> bool {
+    with_registry(|lock| lock.write().unwrap().remove(class_id).is_some())
+}
+
+/// Removes the reflection data of all the classes compiled from the given file,
+/// returning their ids. The source map of the file is not affected, see
+/// [`unregister_source_map`](crate::stack::unregister_source_map).
+pub fn unregister_file(filename: &str) -> Vec<Uuid> {
+    with_registry(|lock| lock.write().unwrap().remove_file(filename))
+}
+
+/// Looks up the reflection data registered for the given class id.
+pub fn get_reflection_data(class_id: &Uuid) -> Option<Arc<ReflectionData>> {
+    with_registry(|lock| lock.read().unwrap().classes.get(class_id).cloned())
 }
@@ -0,0 +1,660 @@
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+/// The bindings imported by a module: the module specifier and the imported
+/// name of each local identifier, `default` for default imports and `*` for
+/// namespace imports.
+pub type Imports = FxHashMap<Id, (String, String)>;
+
+/// Collects the bindings imported by `import` declarations (type-only ones included)
+/// and `import x = require()` declarations.
+pub(crate) fn collect_imports(module: &Module) -> Imports {
+    let mut imports = Imports::default();
+    for decl in module.body.iter().filter_map(|item| item.as_module_decl()) {
+        match decl {
+            ModuleDecl::Import(import) => {
+                let src = import.src.value.to_string();
+                for specifier in &import.specifiers {
+                    let (local, imported) = match specifier {
+                        ImportSpecifier::Named(s) => {
+                            let imported = match &s.imported {
+                                Some(ModuleExportName::Ident(i)) => i.sym.to_string(),
+                                Some(ModuleExportName::Str(s)) => s.value.to_string(),
+                                None => s.local.sym.to_string(),
+                            };
+
+                            (&s.local, imported)
+                        }
+                        ImportSpecifier::Default(s) => (&s.local, "default".to_string()),
+                        ImportSpecifier::Namespace(s) => (&s.local, "*".to_string()),
+                    };
+
+                    imports.insert(local.to_id(), (src.clone(), imported));
+                }
+            }
+            ModuleDecl::TsImportEquals(import) => {
+                if let TsModuleRef::TsExternalModuleRef(r) = &import.module_ref {
+                    imports.insert(
+                        import.id.to_id(),
+                        (r.expr.value.to_string(), "*".to_string()),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    imports
+}
+
+/// The accessibility modifier of a class member or a constructor parameter property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemberAccessibility {
+    Public,
+    Protected,
+    Private,
+}
+
+impl From<Accessibility> for MemberAccessibility {
+    fn from(value: Accessibility) -> Self {
+        match value {
+            Accessibility::Public => MemberAccessibility::Public,
+            Accessibility::Protected => MemberAccessibility::Protected,
+            Accessibility::Private => MemberAccessibility::Private,
+        }
+    }
+}
+
+/// A type referenced by a type annotation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsTypeReference {
+    /// The referenced name, as written (ie: `Foo` or `ns.Foo`).
+    pub name: String,
+    /// The specifier of the module the type is imported from,
+    /// `None` for local and global types.
+    pub specifier: Option<String>,
+    /// The name imported from the module: `default`, `*` or the exported name.
+    pub imported: Option<String>,
+}
+
+/// A TypeScript type annotation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsTypeAnnotation {
+    /// The annotated type, normalized (ie: `Map<string, Foo[]> | null`).
+    pub text: String,
+    pub references: Vec<JsTypeReference>,
+}
+
+impl JsTypeAnnotation {
+    pub(crate) fn new(ty: &TsType, imports: &Imports) -> Self {
+        let mut collector = ReferenceCollector {
+            imports,
+            references: vec![],
+        };
+        ty.visit_with(&mut collector);
+
+        Self {
+            text: type_to_string(ty),
+            references: collector.references,
+        }
+    }
+
+    pub(crate) fn from_ann(ann: Option<&TsTypeAnn>, imports: &Imports) -> Option<Self> {
+        ann.map(|a| Self::new(&a.type_ann, imports))
+    }
+}
+
+/// The type annotation of a parameter pattern.
+pub(crate) fn pat_type_ann(pat: &Pat) -> Option<&TsTypeAnn> {
+    match pat {
+        Pat::Ident(i) => i.type_ann.as_deref(),
+        Pat::Array(a) => a.type_ann.as_deref(),
+        Pat::Object(o) => o.type_ann.as_deref(),
+        Pat::Rest(r) => r.type_ann.as_deref(),
+        Pat::Assign(a) => pat_type_ann(&a.left),
+        _ => None,
+    }
+}
+
+struct ReferenceCollector<'a> {
+    imports: &'a Imports,
+    references: Vec<JsTypeReference>,
+}
+
+impl ReferenceCollector<'_> {
+    fn add(&mut self, name: String, specifier: Option<String>, imported: Option<String>) {
+        if self.references.iter().any(|r| r.name == name) {
+            return;
+        }
+
+        self.references.push(JsTypeReference {
+            name,
+            specifier,
+            imported,
+        });
+    }
+
+    fn add_entity(&mut self, name: &TsEntityName) {
+        let reference = reference(entity_name(name), leftmost_ident(name), self.imports);
+        self.add(reference.name, reference.specifier, reference.imported);
+    }
+}
+
+/// Resolves the module a name is imported from by its leftmost identifier.
+fn reference(name: String, leftmost: &Ident, imports: &Imports) -> JsTypeReference {
+    let (specifier, imported) = imports.get(&leftmost.to_id()).cloned().unzip();
+
+    JsTypeReference {
+        name,
+        specifier,
+        imported,
+    }
+}
+
+/// The leftmost identifier of an entity expression (ie: `Foo` or `ns.Foo`).
+pub(crate) fn entity_expr_ident(expr: &Expr) -> Option<&Ident> {
+    match expr {
+        Expr::Ident(i) => Some(i),
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(_),
+            ..
+        }) => entity_expr_ident(obj),
+        _ => None,
+    }
+}
+
+fn entity_expr_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(i) => Some(i.sym.to_string()),
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => Some(format!("{}.{}", entity_expr_name(obj)?, prop.sym)),
+        _ => None,
+    }
+}
+
+/// The references of the interfaces listed by the `implements` clause of a class.
+pub(crate) fn implemented_interfaces(class: &Class, imports: &Imports) -> Vec<JsTypeReference> {
+    class
+        .implements
+        .iter()
+        .filter_map(|i| {
+            let name = entity_expr_name(&i.expr)?;
+            Some(reference(name, entity_expr_ident(&i.expr)?, imports))
+        })
+        .collect()
+}
+
+impl Visit for ReferenceCollector<'_> {
+    fn visit_ts_type_ref(&mut self, n: &TsTypeRef) {
+        self.add_entity(&n.type_name);
+        n.type_params.visit_with(self);
+    }
+
+    fn visit_ts_type_query(&mut self, n: &TsTypeQuery) {
+        if let TsTypeQueryExpr::TsEntityName(name) = &n.expr_name {
+            self.add_entity(name);
+        }
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_ts_import_type(&mut self, n: &TsImportType) {
+        let name = n.qualifier.as_ref().map(entity_name);
+        let imported = n
+            .qualifier
+            .as_ref()
+            .map(|q| leftmost_ident(q).sym.to_string())
+            .unwrap_or_else(|| "*".to_string());
+
+        self.add(
+            name.unwrap_or_else(|| type_to_string(&TsType::TsImportType(n.clone()))),
+            Some(n.arg.value.to_string()),
+            Some(imported),
+        );
+        n.type_args.visit_with(self);
+    }
+}
+
+fn leftmost_ident(name: &TsEntityName) -> &Ident {
+    match name {
+        TsEntityName::Ident(i) => i,
+        TsEntityName::TsQualifiedName(q) => leftmost_ident(&q.left),
+    }
+}
+
+fn entity_name(name: &TsEntityName) -> String {
+    match name {
+        TsEntityName::Ident(i) => i.sym.to_string(),
+        TsEntityName::TsQualifiedName(q) => format!("{}.{}", entity_name(&q.left), q.right.sym),
+    }
+}
+
+fn join(types: &[Box<TsType>], separator: &str) -> String {
+    types
+        .iter()
+        .map(|t| type_to_string(t))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn type_args(args: Option<&TsTypeParamInstantiation>) -> String {
+    args.map(|a| format!("<{}>", join(&a.params, ", ")))
+        .unwrap_or_default()
+}
+
+fn type_params(params: Option<&TsTypeParamDecl>) -> String {
+    let Some(params) = params else {
+        return String::new();
+    };
+
+    let params = params
+        .params
+        .iter()
+        .map(|p| {
+            let mut param = p.name.sym.to_string();
+            if let Some(constraint) = &p.constraint {
+                param += &format!(" extends {}", type_to_string(constraint));
+            }
+            if let Some(default) = &p.default {
+                param += &format!(" = {}", type_to_string(default));
+            }
+
+            param
+        })
+        .collect::<Vec<_>>();
+
+    format!("<{}>", params.join(", "))
+}
+
+fn ann_suffix(ann: Option<&TsTypeAnn>) -> String {
+    ann.map(|a| format!(": {}", type_to_string(&a.type_ann)))
+        .unwrap_or_default()
+}
+
+fn fn_params(params: &[TsFnParam]) -> String {
+    let params = params
+        .iter()
+        .map(|p| match p {
+            TsFnParam::Ident(i) => format!(
+                "{}{}{}",
+                i.id.sym,
+                if i.id.optional { "?" } else { "" },
+                ann_suffix(i.type_ann.as_deref())
+            ),
+            TsFnParam::Rest(r) => {
+                let name = r.arg.as_ident().map(|i| i.id.sym.to_string());
+                format!(
+                    "...{}{}",
+                    name.as_deref().unwrap_or("_"),
+                    ann_suffix(r.type_ann.as_deref())
+                )
+            }
+            TsFnParam::Array(a) => format!("_{}", ann_suffix(a.type_ann.as_deref())),
+            TsFnParam::Object(o) => format!("_{}", ann_suffix(o.type_ann.as_deref())),
+        })
+        .collect::<Vec<_>>();
+
+    format!("({})", params.join(", "))
+}
+
+fn str_lit(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn key_to_string(key: &Expr, computed: bool) -> String {
+    let key = match key {
+        Expr::Ident(i) => i.sym.to_string(),
+        Expr::Lit(Lit::Str(s)) => str_lit(&s.value),
+        Expr::Lit(Lit::Num(n)) => n.value.to_string(),
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if obj.is_ident() => format!("{}.{}", obj.as_ident().unwrap().sym, prop.sym),
+        _ => "unknown".to_string(),
+    };
+
+    if computed {
+        format!("[{}]", key)
+    } else {
+        key
+    }
+}
+
+fn type_element_to_string(element: &TsTypeElement) -> String {
+    let readonly = |readonly: bool| if readonly { "readonly " } else { "" };
+    let optional = |optional: bool| if optional { "?" } else { "" };
+
+    match element {
+        TsTypeElement::TsCallSignatureDecl(s) => format!(
+            "{}{}{}",
+            type_params(s.type_params.as_deref()),
+            fn_params(&s.params),
+            ann_suffix(s.type_ann.as_deref())
+        ),
+        TsTypeElement::TsConstructSignatureDecl(s) => format!(
+            "new {}{}{}",
+            type_params(s.type_params.as_deref()),
+            fn_params(&s.params),
+            ann_suffix(s.type_ann.as_deref())
+        ),
+        TsTypeElement::TsPropertySignature(s) => format!(
+            "{}{}{}{}",
+            readonly(s.readonly),
+            key_to_string(&s.key, s.computed),
+            optional(s.optional),
+            ann_suffix(s.type_ann.as_deref())
+        ),
+        TsTypeElement::TsGetterSignature(s) => format!(
+            "get {}(){}",
+            key_to_string(&s.key, s.computed),
+            ann_suffix(s.type_ann.as_deref())
+        ),
+        TsTypeElement::TsSetterSignature(s) => format!(
+            "set {}{}",
+            key_to_string(&s.key, s.computed),
+            fn_params(std::slice::from_ref(&s.param))
+        ),
+        TsTypeElement::TsMethodSignature(s) => format!(
+            "{}{}{}{}{}",
+            key_to_string(&s.key, s.computed),
+            optional(s.optional),
+            type_params(s.type_params.as_deref()),
+            fn_params(&s.params),
+            ann_suffix(s.type_ann.as_deref())
+        ),
+        TsTypeElement::TsIndexSignature(s) => {
+            let params = fn_params(&s.params);
+            format!(
+                "{}{}[{}]{}",
+                if s.is_static { "static " } else { "" },
+                readonly(s.readonly),
+                &params[1..params.len() - 1],
+                ann_suffix(s.type_ann.as_deref())
+            )
+        }
+    }
+}
+
+fn plus_minus(modifier: Option<TruePlusMinus>, text: &str) -> String {
+    match modifier {
+        Some(TruePlusMinus::True) => text.to_string(),
+        Some(TruePlusMinus::Plus) => format!("+{}", text),
+        Some(TruePlusMinus::Minus) => format!("-{}", text),
+        None => String::new(),
+    }
+}
+
+/// Prints a type in a normalized form: whitespaces are normalized and
+/// string literals are double-quoted; parentheses are kept as written.
+pub(crate) fn type_to_string(ty: &TsType) -> String {
+    match ty {
+        TsType::TsKeywordType(k) => match k.kind {
+            TsKeywordTypeKind::TsAnyKeyword => "any",
+            TsKeywordTypeKind::TsUnknownKeyword => "unknown",
+            TsKeywordTypeKind::TsNumberKeyword => "number",
+            TsKeywordTypeKind::TsObjectKeyword => "object",
+            TsKeywordTypeKind::TsBooleanKeyword => "boolean",
+            TsKeywordTypeKind::TsBigIntKeyword => "bigint",
+            TsKeywordTypeKind::TsStringKeyword => "string",
+            TsKeywordTypeKind::TsSymbolKeyword => "symbol",
+            TsKeywordTypeKind::TsVoidKeyword => "void",
+            TsKeywordTypeKind::TsUndefinedKeyword => "undefined",
+            TsKeywordTypeKind::TsNullKeyword => "null",
+            TsKeywordTypeKind::TsNeverKeyword => "never",
+            TsKeywordTypeKind::TsIntrinsicKeyword => "intrinsic",
+        }
+        .to_string(),
+        TsType::TsThisType(_) => "this".to_string(),
+        TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(f)) => format!(
+            "{}{} => {}",
+            type_params(f.type_params.as_deref()),
+            fn_params(&f.params),
+            type_to_string(&f.type_ann.type_ann)
+        ),
+        TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsConstructorType(c)) => format!(
+            "{}new {}{} => {}",
+            if c.is_abstract { "abstract " } else { "" },
+            type_params(c.type_params.as_deref()),
+            fn_params(&c.params),
+            type_to_string(&c.type_ann.type_ann)
+        ),
+        TsType::TsTypeRef(r) => format!(
+            "{}{}",
+            entity_name(&r.type_name),
+            type_args(r.type_params.as_deref())
+        ),
+        TsType::TsTypeQuery(q) => {
+            let name = match &q.expr_name {
+                TsTypeQueryExpr::TsEntityName(name) => entity_name(name),
+                TsTypeQueryExpr::Import(i) => type_to_string(&TsType::TsImportType(i.clone())),
+            };
+
+            format!("typeof {}{}", name, type_args(q.type_args.as_deref()))
+        }
+        TsType::TsTypeLit(l) if l.members.is_empty() => "{}".to_string(),
+        TsType::TsTypeLit(l) => format!(
+            "{{ {} }}",
+            l.members
+                .iter()
+                .map(type_element_to_string)
+                .collect::<Vec<_>>()
+                .join("; ")
+        ),
+        TsType::TsArrayType(a) => format!("{}[]", type_to_string(&a.elem_type)),
+        TsType::TsTupleType(t) => format!(
+            "[{}]",
+            t.elem_types
+                .iter()
+                .map(|e| match e.label.as_ref().and_then(|l| l.as_ident()) {
+                    Some(label) => format!("{}: {}", label.id.sym, type_to_string(&e.ty)),
+                    None => type_to_string(&e.ty),
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        TsType::TsOptionalType(o) => format!("{}?", type_to_string(&o.type_ann)),
+        TsType::TsRestType(r) => format!("...{}", type_to_string(&r.type_ann)),
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(u)) => {
+            join(&u.types, " | ")
+        }
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsIntersectionType(i)) => {
+            join(&i.types, " & ")
+        }
+        TsType::TsConditionalType(c) => format!(
+            "{} extends {} ? {} : {}",
+            type_to_string(&c.check_type),
+            type_to_string(&c.extends_type),
+            type_to_string(&c.true_type),
+            type_to_string(&c.false_type)
+        ),
+        TsType::TsInferType(i) => match &i.type_param.constraint {
+            Some(constraint) => format!(
+                "infer {} extends {}",
+                i.type_param.name.sym,
+                type_to_string(constraint)
+            ),
+            None => format!("infer {}", i.type_param.name.sym),
+        },
+        TsType::TsParenthesizedType(p) => format!("({})", type_to_string(&p.type_ann)),
+        TsType::TsTypeOperator(o) => {
+            let op = match o.op {
+                TsTypeOperatorOp::KeyOf => "keyof",
+                TsTypeOperatorOp::Unique => "unique",
+                TsTypeOperatorOp::ReadOnly => "readonly",
+            };
+
+            format!("{} {}", op, type_to_string(&o.type_ann))
+        }
+        TsType::TsIndexedAccessType(i) => format!(
+            "{}{}[{}]",
+            if i.readonly { "readonly " } else { "" },
+            type_to_string(&i.obj_type),
+            type_to_string(&i.index_type)
+        ),
+        TsType::TsMappedType(m) => {
+            let mut mapped = format!(
+                "[{} in {}",
+                m.type_param.name.sym,
+                m.type_param
+                    .constraint
+                    .as_deref()
+                    .map(type_to_string)
+                    .unwrap_or_default()
+            );
+            if let Some(name_type) = &m.name_type {
+                mapped += &format!(" as {}", type_to_string(name_type));
+            }
+
+            format!(
+                "{{ {}{}]{}: {} }}",
+                plus_minus(m.readonly, "readonly "),
+                mapped,
+                plus_minus(m.optional, "?"),
+                m.type_ann
+                    .as_deref()
+                    .map(type_to_string)
+                    .unwrap_or_else(|| "any".to_string())
+            )
+        }
+        TsType::TsLitType(l) => match &l.lit {
+            TsLit::Number(n) => n.value.to_string(),
+            TsLit::Str(s) => str_lit(&s.value),
+            TsLit::Bool(b) => b.value.to_string(),
+            TsLit::BigInt(b) => format!("{}n", b.value),
+            TsLit::Tpl(t) => {
+                let mut tpl = String::from("`");
+                for (idx, quasi) in t.quasis.iter().enumerate() {
+                    tpl += &*quasi.raw;
+                    if let Some(ty) = t.types.get(idx) {
+                        tpl += &format!("${{{}}}", type_to_string(ty));
+                    }
+                }
+
+                tpl + "`"
+            }
+        },
+        TsType::TsTypePredicate(p) => {
+            let param = match &p.param_name {
+                TsThisTypeOrIdent::TsThisType(_) => "this".to_string(),
+                TsThisTypeOrIdent::Ident(i) => i.sym.to_string(),
+            };
+            let asserts = if p.asserts { "asserts " } else { "" };
+
+            match &p.type_ann {
+                Some(ann) => format!("{}{} is {}", asserts, param, type_to_string(&ann.type_ann)),
+                None => format!("{}{}", asserts, param),
+            }
+        }
+        TsType::TsImportType(i) => {
+            let mut import = format!("import({})", str_lit(&i.arg.value));
+            if let Some(qualifier) = &i.qualifier {
+                import += &format!(".{}", entity_name(qualifier));
+            }
+
+            format!("{}{}", import, type_args(i.type_args.as_deref()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::CodeParser;
+    use crate::reflection::{get_reflection_data, MemberAccessibility};
+    use crate::testing::uuid::reset_test_uuid;
+
+    #[test]
+    pub fn should_capture_type_annotations() -> anyhow::Result<()> {
+        reset_test_uuid();
+
+        let code = r#"
+import type { Logger } from './logger';
+import Repository, * as models from '../models';
+
+export default class Service {
+    private readonly cache: Map<string, models.User[]> | null = null;
+    protected static count: number;
+
+    constructor(private readonly logger: Logger, public repo: Repository<models.User>, limit = 10) {}
+
+    find(id: string | number, options?: { strict: boolean; 'max-age'?: number }): Promise<models.User> {}
+    get size(): number {}
+    set size(value: number) {}
+    handle(callback: (err: Error, ...args: unknown[]) => void, kind: 'a' | "b" = 'a'): keyof typeof models {}
+    accessor label: string = '';
+}
+"#;
+
+        let result = code
+            .parse_program(Some("service.ts"))?
+            .compile(Default::default())?;
+        let data = get_reflection_data(&result.class_ids[0]).unwrap();
+        let text = |ty: &Option<crate::reflection::JsTypeAnnotation>| {
+            ty.as_ref().map(|t| t.text.clone()).unwrap_or_default()
+        };
+
+        let cache = &data.members[0];
+        assert_eq!(text(&cache.type_), "Map<string, models.User[]> | null");
+        assert_eq!(cache.accessibility, Some(MemberAccessibility::Private));
+        assert!(cache.is_readonly);
+        let references = &cache.type_.as_ref().unwrap().references;
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[0].name, "Map");
+        assert_eq!(references[0].specifier, None);
+        assert_eq!(references[1].name, "models.User");
+        assert_eq!(references[1].specifier.as_deref(), Some("../models"));
+        assert_eq!(references[1].imported.as_deref(), Some("*"));
+
+        let count = &data.members[1];
+        assert_eq!(text(&count.type_), "number");
+        assert_eq!(count.accessibility, Some(MemberAccessibility::Protected));
+        assert!(!count.is_readonly);
+
+        let constructor = &data.members[2];
+        let logger = &constructor.params[0];
+        assert_eq!(text(&logger.type_), "Logger");
+        assert_eq!(logger.accessibility, Some(MemberAccessibility::Private));
+        assert!(logger.is_readonly);
+        let logger_ref = &logger.type_.as_ref().unwrap().references[0];
+        assert_eq!(logger_ref.specifier.as_deref(), Some("./logger"));
+        assert_eq!(logger_ref.imported.as_deref(), Some("Logger"));
+
+        let repo = &constructor.params[1];
+        assert_eq!(text(&repo.type_), "Repository<models.User>");
+        assert_eq!(repo.accessibility, Some(MemberAccessibility::Public));
+        let repo_ref = &repo.type_.as_ref().unwrap().references[0];
+        assert_eq!(repo_ref.imported.as_deref(), Some("default"));
+        assert!(constructor.params[2].type_.is_none());
+
+        let find = &data.members[3];
+        assert_eq!(text(&find.params[0].type_), "string | number");
+        assert_eq!(
+            text(&find.params[1].type_),
+            r#"{ strict: boolean; "max-age"?: number }"#
+        );
+        assert_eq!(text(&find.return_type), "Promise<models.User>");
+
+        assert_eq!(text(&data.members[4].return_type), "number");
+        assert_eq!(text(&data.members[5].params[0].type_), "number");
+
+        let handle = &data.members[6];
+        assert_eq!(
+            text(&handle.params[0].type_),
+            "(err: Error, ...args: unknown[]) => void"
+        );
+        assert_eq!(text(&handle.params[1].type_), r#""a" | "b""#);
+        assert_eq!(text(&handle.return_type), "keyof typeof models");
+
+        let label = &data.members[7];
+        assert_eq!(text(&label.type_), "string");
+
+        Ok(())
+    }
+}
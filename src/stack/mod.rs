@@ -1,22 +1,26 @@
+//! Stack trace remapping through the source maps of the compiled files.
+
 mod trace;
 
 use lazy_static::lazy_static;
 use rustc_hash::FxHashMap;
-use std::sync::RwLock;
+use std::sync::Mutex;
 
-pub(crate) use trace::remap_stack_trace;
+pub use trace::remap_stack_trace;
 
+// Source views cache lines with interior mutability: the map is not `Sync`,
+// hence the mappings are guarded by a mutex rather than a read-write lock.
 struct InternalSourceMap(sourcemap::SourceMap);
 unsafe impl Send for InternalSourceMap {}
-unsafe impl Sync for InternalSourceMap {}
 
 lazy_static! {
-    static ref FILE_MAPPINGS: RwLock<FxHashMap<String, InternalSourceMap>> =
-        RwLock::new(Default::default());
+    static ref FILE_MAPPINGS: Mutex<FxHashMap<String, InternalSourceMap>> =
+        Mutex::new(Default::default());
 }
 
+/// A stack frame, as described by the V8 stack trace API.
 #[derive(Debug)]
-pub(crate) struct Frame {
+pub struct Frame {
     pub filename: Option<String>,
     pub line_no: u32,
     pub col_no: u32,
@@ -32,7 +36,22 @@ pub(crate) struct Frame {
     pub string_repr: String,
 }
 
+/// Registers the source map used to remap the stack frames of the given file.
+/// Compiling a program with a filename registers its source map automatically.
 pub fn register_source_map(filename: String, srcmap: sourcemap::SourceMap) {
-    let mut mappings = FILE_MAPPINGS.write().unwrap();
+    let mut mappings = FILE_MAPPINGS.lock().unwrap();
     mappings.insert(filename, InternalSourceMap(srcmap));
 }
+
+/// Returns the source map registered for the given file.
+pub(crate) fn get_source_map(filename: &str) -> Option<sourcemap::SourceMap> {
+    let mappings = FILE_MAPPINGS.lock().unwrap();
+    mappings.get(filename).map(|m| m.0.clone())
+}
+
+/// Removes the source map registered for the given file.
+/// Returns whether a source map was registered.
+pub fn unregister_source_map(filename: &str) -> bool {
+    let mut mappings = FILE_MAPPINGS.lock().unwrap();
+    mappings.remove(filename).is_some()
+}
@@ -1,13 +1,11 @@
 use super::{Frame, FILE_MAPPINGS};
+use crate::parser::ANONYMOUS_PREFIX;
 
 /// Prepares stack trace using V8 stack trace API.
-pub(crate) fn remap_stack_trace(
-    error_message: &str,
-    stack: &[Frame],
-    previous: Option<String>,
-) -> String {
+/// Frames of registered files are remapped to the original source positions.
+pub fn remap_stack_trace(error_message: &str, stack: &[Frame], previous: Option<String>) -> String {
     let mut processed = false;
-    let mappings = FILE_MAPPINGS.read().unwrap();
+    let mappings = FILE_MAPPINGS.lock().unwrap();
     let new_stack = stack
         .iter()
         .filter_map(|frame| {
@@ -42,19 +40,21 @@ pub(crate) fn remap_stack_trace(
             );
             let mut function_name = frame.function_name.as_ref().cloned();
             if let Some(sv) = source_map.0.get_source_view(token.get_src_id()) {
+                // Names inferred at compile time are kept when the original cannot be found.
                 function_name = source_map
                     .0
                     .get_original_function_name(
                         line_no,
                         col_no,
-                        function_name.unwrap_or_default().as_str(),
+                        function_name.as_deref().unwrap_or_default(),
                         sv,
                     )
-                    .map(|f| f.to_string());
+                    .map(|f| f.to_string())
+                    .or(function_name);
             }
 
             if let Some(fun) = &function_name {
-                if fun.starts_with("_anonymous_xΞ") {
+                if fun.starts_with(ANONYMOUS_PREFIX) {
                     function_name = None;
                 }
             }
@@ -117,6 +117,72 @@ where
     })
 }
 
+/// Executes a compiled (CommonJS) program with `node`, then runs the given assertions.
+/// The assertions are the body of an async function receiving the program `exports`
+/// and the node `assert` module.
+/// The runtime helpers are loaded from `lib/`; the reflection decorator is stubbed,
+/// as it requires the wasm module.
+pub fn exec_compiled(test_name: &str, code: &str, assertions: &str) {
+    let lib = Path::new(env!("CARGO_MANIFEST_DIR")).join("lib");
+    let helper = |name: &str| format!("{:?}", lib.join(name).to_string_lossy());
+
+    let src = format!(
+        "global.__jymfony = global.__jymfony || {{ JObject: class {{ __construct() {{}} }} }};
+global._apply_decs_2203_r = require({})._;
+global._construct_jobject = require({})._;
+global.__jymfony_metadata = require({})._;
+global.__jymfony_reflect = () => () => {{}};
+
+const program = {{}};
+(function (exports) {{
+{}
+}})(program);
+
+(async function (exports, assert) {{
+{}
+}})(program, require('node:assert')).catch((e) => {{
+    console.error(e);
+    process.exitCode = 1;
+}});
+",
+        helper("_apply_decs_2203_r.js"),
+        helper("_construct_jobject.js"),
+        helper("metadata.js"),
+        code,
+        assertions,
+    );
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("testing")
+        .join(test_name);
+
+    create_dir_all(&root).expect("failed to create parent directory for temp directory");
+
+    let tmp_dir = tempdir_in(&root).expect("failed to create a temp directory");
+    let path = tmp_dir.path().join(format!("{}.test.cjs", test_name));
+    fs::write(&path, &src).expect("failed to write to temp file");
+
+    let node = find_executable("node").expect("failed to find `node` from path");
+    let output = Command::new(node)
+        .arg(&path)
+        .current_dir(&root)
+        .output()
+        .expect("failed to run node");
+
+    if output.status.success() {
+        return;
+    }
+
+    println!(">>>>> {} <<<<<\n{}", Color::Green.paint("Code"), src);
+    println!(">>>>> {} <<<<<", Color::Red.paint("Stderr"));
+    println!("{}", String::from_utf8_lossy(&output.stderr));
+
+    let dir_name = path.display().to_string();
+    ::std::mem::forget(tmp_dir);
+    panic!("Execution failed: {dir_name}")
+}
+
 fn exec_with_node_test_runner(test_name: &str, src: &str) -> Result<(), ()> {
     let root = Path::new(env!("CARGO_MANIFEST_DIR"))
         .join("target")
@@ -1,4 +1,11 @@
-use crate::parser::{CodeParser, CompileOptions};
+use crate::parser::{
+    CodeParser, CompileOptions, CompileResult, JsxRuntime, ModuleKind, ReflectionMode,
+    SourceMapMode, SourceMapOptions,
+};
+use crate::{Diagnostic, SyntaxError};
+use js_sys::Reflect;
+use serde::Serialize;
+use swc_ecma_ast::EsVersion;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen(typescript_custom_section)]
@@ -8,6 +15,54 @@ interface CompileOptions {
     namespace?: string;
     asFunction?: boolean;
     asModule?: boolean;
+    jsx?: JsxOptions;
+    moduleKind?: 'module' | 'script';
+    sourceMap?: SourceMapOptions;
+    minify?: boolean;
+    deterministicIds?: boolean;
+    reflection?: 'registry' | 'inline';
+    emitDecoratorMetadata?: boolean;
+    /**
+     * Applies the interfaces listed by the `implements` clause of TypeScript classes as mixins.
+     * Only the interfaces declared with `getInterface` or imported (not `import type`) are applied.
+     * Requires the global `mix` function (ie: `@jymfony/util`).
+     */
+    implementsMixins?: boolean;
+    target?: 'es2017' | 'es2018' | 'es2019' | 'es2020' | 'es2021' | 'es2022' | 'esnext';
+}
+
+interface SourceMapOptions {
+    mode?: 'inline' | 'external' | 'hidden' | 'none';
+    sourceRoot?: string;
+    sourcesContent?: boolean;
+    file?: string;
+    sourcePath?: string;
+}
+
+interface JsxOptions {
+    runtime?: 'classic' | 'automatic';
+    pragma?: string;
+    pragmaFrag?: string;
+    importSource?: string;
+}
+
+interface CompileResult {
+    code: string;
+    sourceMap?: string;
+    classIds: string[];
+    dependencies: string[];
+    warnings: Diagnostic[];
+}
+
+interface Diagnostic {
+    filename?: string;
+    span: { start: number; end: number };
+    line: number;
+    column: number;
+    kind: string;
+    message: string;
+    severity: 'error' | 'warning';
+    codeFrame?: string;
 }
 "#;
 
@@ -27,6 +82,259 @@ extern "C" {
 
     #[wasm_bindgen(structural, method, getter, js_name = "asModule")]
     fn as_module(this: &WasmCompileOptions) -> Option<bool>;
+
+    #[wasm_bindgen(structural, method, getter)]
+    fn jsx(this: &WasmCompileOptions) -> Option<WasmJsxOptions>;
+
+    #[wasm_bindgen(structural, method, getter, js_name = "moduleKind")]
+    fn module_kind(this: &WasmCompileOptions) -> Option<String>;
+
+    #[wasm_bindgen(structural, method, getter, js_name = "sourceMap")]
+    fn source_map(this: &WasmCompileOptions) -> Option<WasmSourceMapOptions>;
+
+    #[wasm_bindgen(structural, method, getter)]
+    fn target(this: &WasmCompileOptions) -> Option<String>;
+
+    #[wasm_bindgen(structural, method, getter)]
+    fn minify(this: &WasmCompileOptions) -> Option<bool>;
+
+    #[wasm_bindgen(structural, method, getter, js_name = "deterministicIds")]
+    fn deterministic_ids(this: &WasmCompileOptions) -> Option<bool>;
+
+    #[wasm_bindgen(structural, method, getter)]
+    fn reflection(this: &WasmCompileOptions) -> Option<String>;
+
+    #[wasm_bindgen(structural, method, getter, js_name = "emitDecoratorMetadata")]
+    fn emit_decorator_metadata(this: &WasmCompileOptions) -> Option<bool>;
+
+    #[wasm_bindgen(structural, method, getter, js_name = "implementsMixins")]
+    fn implements_mixins(this: &WasmCompileOptions) -> Option<bool>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "JsxOptions")]
+    pub type WasmJsxOptions;
+
+    #[wasm_bindgen(structural, method, getter)]
+    fn runtime(this: &WasmJsxOptions) -> Option<String>;
+
+    #[wasm_bindgen(structural, method, getter)]
+    fn pragma(this: &WasmJsxOptions) -> Option<String>;
+
+    #[wasm_bindgen(structural, method, getter, js_name = "pragmaFrag")]
+    fn pragma_frag(this: &WasmJsxOptions) -> Option<String>;
+
+    #[wasm_bindgen(structural, method, getter, js_name = "importSource")]
+    fn import_source(this: &WasmJsxOptions) -> Option<String>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "SourceMapOptions")]
+    pub type WasmSourceMapOptions;
+
+    #[wasm_bindgen(structural, method, getter)]
+    fn mode(this: &WasmSourceMapOptions) -> Option<String>;
+
+    #[wasm_bindgen(structural, method, getter, js_name = "sourceRoot")]
+    fn source_root(this: &WasmSourceMapOptions) -> Option<String>;
+
+    #[wasm_bindgen(structural, method, getter, js_name = "sourcesContent")]
+    fn sources_content(this: &WasmSourceMapOptions) -> Option<bool>;
+
+    #[wasm_bindgen(structural, method, getter)]
+    fn file(this: &WasmSourceMapOptions) -> Option<String>;
+
+    #[wasm_bindgen(structural, method, getter, js_name = "sourcePath")]
+    fn source_path(this: &WasmSourceMapOptions) -> Option<String>;
+}
+
+impl TryFrom<&WasmSourceMapOptions> for SourceMapOptions {
+    type Error = JsError;
+
+    fn try_from(value: &WasmSourceMapOptions) -> Result<Self, Self::Error> {
+        let mode = match value.mode().as_deref() {
+            None | Some("inline") => SourceMapMode::Inline,
+            Some("external") => SourceMapMode::External,
+            Some("hidden") => SourceMapMode::Hidden,
+            Some("none") => SourceMapMode::None,
+            Some(mode) => {
+                return Err(JsError::new(&format!(
+                    "Unknown source map mode \"{}\": expected one of \"inline\", \"external\", \"hidden\" or \"none\"",
+                    mode
+                )))
+            }
+        };
+
+        Ok(SourceMapOptions {
+            mode,
+            source_root: value.source_root(),
+            sources_content: value.sources_content().unwrap_or_default(),
+            file: value.file(),
+            source_path: value.source_path(),
+        })
+    }
+}
+
+impl TryFrom<&WasmJsxOptions> for JsxRuntime {
+    type Error = JsError;
+
+    fn try_from(value: &WasmJsxOptions) -> Result<Self, Self::Error> {
+        match value.runtime().as_deref() {
+            None | Some("classic") => Ok(JsxRuntime::Classic {
+                pragma: value.pragma(),
+                pragma_frag: value.pragma_frag(),
+            }),
+            Some("automatic") => Ok(JsxRuntime::Automatic {
+                import_source: value.import_source(),
+            }),
+            Some(runtime) => Err(JsError::new(&format!(
+                "Unknown JSX runtime \"{}\": expected \"classic\" or \"automatic\"",
+                runtime
+            ))),
+        }
+    }
+}
+
+impl TryFrom<Option<&WasmCompileOptions>> for CompileOptions {
+    type Error = JsValue;
+
+    fn try_from(opts: Option<&WasmCompileOptions>) -> Result<Self, Self::Error> {
+        let debug = opts.and_then(|c| c.debug()).unwrap_or_default();
+        let namespace = opts.and_then(|c| c.namespace());
+        let as_function = opts.and_then(|c| c.as_function()).unwrap_or_default();
+        let as_module = opts.and_then(|c| c.as_module()).unwrap_or_default();
+        let jsx = opts
+            .and_then(|c| c.jsx())
+            .map(|j| JsxRuntime::try_from(&j))
+            .transpose()?
+            .unwrap_or_default();
+        let module_kind = match opts.and_then(|c| c.module_kind()).as_deref() {
+            None => None,
+            Some("module") => Some(ModuleKind::Module),
+            Some("script") => Some(ModuleKind::Script),
+            Some(kind) => {
+                return Err(JsError::new(&format!(
+                    "Unknown module kind \"{}\": expected \"module\" or \"script\"",
+                    kind
+                ))
+                .into())
+            }
+        };
+        let source_map = opts
+            .and_then(|c| c.source_map())
+            .map(|s| SourceMapOptions::try_from(&s))
+            .transpose()?
+            .unwrap_or_default();
+        let target = match opts.and_then(|c| c.target()).as_deref() {
+            None => None,
+            Some("es2017") => Some(EsVersion::Es2017),
+            Some("es2018") => Some(EsVersion::Es2018),
+            Some("es2019") => Some(EsVersion::Es2019),
+            Some("es2020") => Some(EsVersion::Es2020),
+            Some("es2021") => Some(EsVersion::Es2021),
+            Some("es2022") => Some(EsVersion::Es2022),
+            Some("esnext") => Some(EsVersion::EsNext),
+            Some(target) => {
+                return Err(JsError::new(&format!(
+                    "Unknown target \"{}\": expected one of \"es2017\" to \"es2022\" or \"esnext\"",
+                    target
+                ))
+                .into())
+            }
+        };
+        let minify = opts.and_then(|c| c.minify()).unwrap_or_default();
+        let deterministic_ids = opts.and_then(|c| c.deterministic_ids()).unwrap_or_default();
+        let reflection = match opts.and_then(|c| c.reflection()).as_deref() {
+            None | Some("registry") => ReflectionMode::Registry,
+            Some("inline") => ReflectionMode::Inline,
+            Some(mode) => {
+                return Err(JsError::new(&format!(
+                    "Unknown reflection mode \"{}\": expected \"registry\" or \"inline\"",
+                    mode
+                ))
+                .into())
+            }
+        };
+
+        let emit_decorator_metadata = opts
+            .and_then(|c| c.emit_decorator_metadata())
+            .unwrap_or_default();
+        let implements_mixins = opts.and_then(|c| c.implements_mixins()).unwrap_or_default();
+
+        Ok(CompileOptions {
+            debug,
+            namespace,
+            as_function,
+            as_module,
+            jsx,
+            module_kind,
+            source_map,
+            target,
+            minify,
+            deterministic_ids,
+            reflection,
+            emit_decorator_metadata,
+            implements_mixins,
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsCompileResult {
+    pub code: String,
+    pub source_map: Option<String>,
+    pub class_ids: Vec<String>,
+    pub dependencies: Vec<String>,
+    pub warnings: Vec<Diagnostic>,
+}
+
+impl From<CompileResult> for JsCompileResult {
+    fn from(value: CompileResult) -> Self {
+        Self {
+            code: value.code,
+            source_map: value.source_map,
+            class_ids: value.class_ids.iter().map(|id| id.to_string()).collect(),
+            dependencies: value.dependencies,
+            warnings: value.warnings,
+        }
+    }
+}
+
+/// Converts a parse error into a JS error carrying the diagnostics.
+pub(crate) fn parse_error(e: anyhow::Error, filename: Option<&str>) -> JsValue {
+    let error = js_sys::Error::new(&format!(
+        "{} while parsing {}",
+        e,
+        filename.unwrap_or("<no filename provided>")
+    ));
+
+    if let Some(syntax_error) = e.downcast_ref::<SyntaxError>() {
+        if let Ok(diagnostics) = serde_wasm_bindgen::to_value(syntax_error.diagnostics()) {
+            let _ = Reflect::set(&error, &"diagnostics".into(), &diagnostics);
+        }
+
+        if let Some(code_frame) = syntax_error.code_frame() {
+            let _ = Reflect::set(&error, &"codeFrame".into(), &code_frame.into());
+        }
+    }
+
+    error.into()
+}
+
+fn compile_program(
+    source: String,
+    filename: Option<String>,
+    opts: Option<WasmCompileOptions>,
+) -> Result<CompileResult, JsValue> {
+    let opts = CompileOptions::try_from(opts.as_ref())?;
+    let program = source
+        .parse_program(filename.as_deref())
+        .map_err(|e| parse_error(e, filename.as_deref()))?;
+
+    program.compile(opts).map_err(|e| JsError::from(e).into())
 }
 
 #[wasm_bindgen(js_name = compile)]
@@ -34,35 +342,18 @@ pub fn compile(
     source: String,
     filename: Option<String>,
     opts: Option<WasmCompileOptions>,
-) -> Result<String, JsError> {
-    let debug = opts.as_ref().and_then(|c| c.debug()).unwrap_or_default();
-    let namespace = opts.as_ref().and_then(|c| c.namespace());
-    let as_function = opts
-        .as_ref()
-        .and_then(|c| c.as_function())
-        .unwrap_or_default();
-    let as_module = opts
-        .as_ref()
-        .and_then(|c| c.as_module())
-        .unwrap_or_default();
-
-    let program = match source.parse_program(filename.as_deref()) {
-        Ok(p) => p,
-        Err(e) => {
-            return Err(JsError::new(&format!(
-                "{} while parsing {}",
-                e,
-                filename.as_deref().unwrap_or("<no filename provided>")
-            )));
-        }
-    };
-
-    Ok(program.compile(CompileOptions {
-        debug,
-        namespace,
-        as_function,
-        as_module,
-    })?)
+) -> Result<String, JsValue> {
+    Ok(compile_program(source, filename, opts)?.code)
+}
+
+#[wasm_bindgen(js_name = compileDetailed)]
+pub fn compile_detailed(
+    source: String,
+    filename: Option<String>,
+    opts: Option<WasmCompileOptions>,
+) -> Result<JsValue, JsValue> {
+    let result = JsCompileResult::from(compile_program(source, filename, opts)?);
+    Ok(serde_wasm_bindgen::to_value(&result)?)
 }
 
 #[wasm_bindgen(js_name = isValidIdentifier)]
@@ -0,0 +1,23 @@
+use crate::parser::CodeParser;
+use crate::wasm::compile::parse_error;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const ITEXT_STYLE: &'static str = r#"
+interface Dependency {
+    specifier: string;
+    kind: 'import' | 'reExport' | 'require' | 'dynamicImport';
+    optional: boolean;
+    span: { start: number; end: number };
+    names: string[];
+}
+"#;
+
+#[wasm_bindgen(js_name = getDependencies)]
+pub fn get_dependencies(source: String, filename: Option<String>) -> Result<JsValue, JsValue> {
+    let program = source
+        .parse_program(filename.as_deref())
+        .map_err(|e| parse_error(e, filename.as_deref()))?;
+
+    Ok(serde_wasm_bindgen::to_value(&program.dependencies())?)
+}
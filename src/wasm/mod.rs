@@ -1,4 +1,5 @@
 mod compile;
+mod dependencies;
 mod reflection;
 mod stack_trace;
 
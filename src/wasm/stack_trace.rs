@@ -1,5 +1,5 @@
 use crate::stack::remap_stack_trace;
-use crate::Frame;
+use crate::stack::Frame;
 use js_sys::*;
 use std::fmt::{Debug, Formatter};
 use std::iter::Iterator;
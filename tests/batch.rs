@@ -0,0 +1,35 @@
+use jymfony_compiler::parser::compile_many;
+use jymfony_compiler::reflection::{get_reflection_data, unregister_file};
+
+/// The registry is thread local in the unit tests: the classes registered
+/// by the batch workers are looked up from the calling thread here.
+#[test]
+pub fn should_register_batch_classes_into_the_shared_registry() {
+    let inputs = (0..8)
+        .map(|i| {
+            (
+                format!("export default class Batch{} {{}}\n", i),
+                Some(format!("batch{}.js", i)),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let result = compile_many(inputs, &Default::default());
+    assert_eq!(result.stats.succeeded, 8);
+
+    for (i, file) in result.files.iter().enumerate() {
+        let compiled = file.result.as_ref().unwrap();
+        assert_eq!(compiled.class_ids.len(), 1);
+
+        let data = get_reflection_data(&compiled.class_ids[0]).unwrap();
+        assert_eq!(data.name, format!("Batch{}", i));
+        assert_eq!(data.filename.as_deref(), file.filename.as_deref());
+    }
+
+    for file in &result.files {
+        unregister_file(file.filename.as_deref().unwrap());
+    }
+
+    let compiled = result.files[0].result.as_ref().unwrap();
+    assert!(get_reflection_data(&compiled.class_ids[0]).is_none());
+}
@@ -1,4 +1,4 @@
-const { compile } = require('../..');
+const { compile, compileDetailed, getDependencies } = require('../..');
 
 describe('CommonJS', () => {
     it('should compile exports correctly', () => {
@@ -30,4 +30,46 @@ _export(exports, {
 });
 `);
     });
+
+    it('should return compile details', () => {
+        const program = `
+import { parse } from 'url';
+const fs = require('fs');
+
+export default class x {}
+`;
+
+        const result = compileDetailed(program, 'x.js');
+        expect(result.code).toContain('class x extends');
+        expect(result.dependencies).toEqual(['url', 'fs']);
+        expect(result.classIds).toHaveLength(1);
+        expect(JSON.parse(result.sourceMap).version).toEqual(3);
+        expect(result.warnings).toEqual([]);
+    });
+
+    it('should list dependencies', () => {
+        const program = `
+import Redis from 'ioredis' with { optional: true };
+export { parse } from 'url';
+const fs = require('fs');
+`;
+
+        const dependencies = getDependencies(program, 'x.js');
+        expect(dependencies).toHaveLength(3);
+        expect(dependencies[0]).toMatchObject({
+            specifier: 'ioredis',
+            kind: 'import',
+            optional: true,
+            names: ['default'],
+        });
+        expect(dependencies[1]).toMatchObject({
+            specifier: 'url',
+            kind: 'reExport',
+            names: ['parse'],
+        });
+        expect(dependencies[2]).toMatchObject({
+            specifier: 'fs',
+            kind: 'require',
+        });
+    });
 });
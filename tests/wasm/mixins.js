@@ -0,0 +1,46 @@
+const { compile } = require('../..');
+const { runInThisContext } = require('node:vm');
+
+describe('Implements mixins', () => {
+    it('should apply the implemented interfaces as mixins', () => {
+        const program = `
+import { Countable } from './countable';
+import type { Serializable } from './serializable';
+
+interface Shape {}
+
+export const Comparable = getInterface(class Comparable {});
+
+export class Base {}
+
+export class Collection extends Base implements Comparable, Countable, Serializable, Shape {}
+
+export class Entity implements Comparable {
+    constructor(public id: number) {}
+}
+`;
+
+        const compiled = compile(program, 'mixins.ts', {
+            implementsMixins: true,
+        });
+
+        const Countable = getInterface(class Countable {});
+        const modules = { './countable': { Countable } };
+
+        const exports = {};
+        runInThisContext('(function(exports, require) {\n' + compiled + '\n})')(
+            exports,
+            (specifier) => modules[specifier],
+        );
+
+        const { Base, Collection, Comparable, Entity } = exports;
+        expect(new Collection()).toBeInstanceOf(Base);
+        expect(new Collection()).toBeInstanceOf(Comparable);
+        expect(new Collection()).toBeInstanceOf(Countable);
+        expect(new Collection()).toBeInstanceOf(__jymfony.JObject);
+        expect(new Entity(42)).toBeInstanceOf(Comparable);
+        expect(new Entity(42)).toBeInstanceOf(__jymfony.JObject);
+        expect(new Entity(42).id).toEqual(42);
+        expect(new Base()).not.toBeInstanceOf(Comparable);
+    });
+});
@@ -1,4 +1,4 @@
-const { isValidIdentifier, getArgumentNames } = require('../..');
+const { compile, isValidIdentifier, getArgumentNames } = require('../..');
 
 describe('Parser', () => {
     const identifiers = ['x', 'y', 'ident'];
@@ -40,4 +40,31 @@ describe('Parser', () => {
             getArgumentNames('module.exports = function () {}'),
         ).toThrowError();
     });
+
+    it('should expose diagnostics on syntax errors', () => {
+        const program = `
+export const a = 010;
+export const b = 011;
+`;
+
+        let error;
+        try {
+            compile(program, 'a.js');
+        } catch (e) {
+            error = e;
+        }
+
+        expect(error).toBeInstanceOf(Error);
+        expect(error.diagnostics).toHaveLength(2);
+        expect(error.diagnostics[0]).toMatchObject({
+            filename: 'a.js',
+            line: 2,
+            column: 18,
+            severity: 'error',
+        });
+        expect(error.diagnostics[1]).toMatchObject({
+            line: 3,
+            column: 18,
+        });
+    });
 });
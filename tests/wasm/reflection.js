@@ -1,4 +1,10 @@
-const { compile } = require('../..');
+const {
+    compile,
+    compileDetailed,
+    exportReflectionData,
+    getDesignMetadata,
+    importReflectionData,
+} = require('../..');
 const { runInThisContext } = require('node:vm');
 
 describe('Reflection', () => {
@@ -94,4 +100,167 @@ return x[Symbol.metadata].act[Symbol.parameters][0].type;
         expect(defaults.parameters[2].default).toEqual('test');
         expect(defaults.parameters[6].default).toEqual(null);
     });
+
+    it('should export and import reflection data', () => {
+        const { classIds } = compileDetailed(
+            'export default class Exported {}',
+            'exported.js',
+        );
+
+        const exported = exportReflectionData(classIds);
+        expect(exported).toBeInstanceOf(Uint8Array);
+
+        expect(importReflectionData(exported)).toEqual(classIds);
+        expect(() => importReflectionData(new Uint8Array([1, 2, 3]))).toThrow();
+    });
+
+    it('should read inlined reflection data', () => {
+        const { getReflectionData } = require('../../lib/reflection');
+
+        const program = `
+/** inline docblock */
+export default class Inlined {
+    /** method docblock */
+    method(a, b = 'test') {}
+}
+`;
+
+        const compiled = compile(program, 'inlined.js', {
+            reflection: 'inline',
+        });
+        expect(compiled).toContain('fqcn: "Inlined"');
+
+        const exports = {};
+        runInThisContext('(function(exports) {\n' + compiled + '\n})')(exports);
+
+        const data = getReflectionData(exports['default']);
+        expect(data.fqcn).toEqual('Inlined');
+        expect(data.docblock).toEqual('/** inline docblock */');
+
+        const method = data.members.find((o) => o.name === 'method');
+        expect(method.docblock).toEqual('/** method docblock */');
+        expect(method.parsedDocblock.summary).toEqual('method docblock');
+        expect(data.parsedDocblock).toEqual({
+            summary: 'inline docblock',
+            tags: [],
+        });
+        expect(method.parameters[1].default).toEqual('test');
+    });
+
+    it('should describe members before the class is evaluated', () => {
+        const { getReflectionData } = require('../../lib/reflection');
+
+        const { classIds } = compileDetailed(
+            `
+export default class NotEvaluated {
+    static #count = 0;
+    get value() {}
+    [Symbol.iterator]() {}
+    [Symbol.for('registered')](a, b = 2) {}
+}
+`,
+            'not-evaluated.js',
+        );
+
+        const data = getReflectionData(classIds[0]);
+        expect(data.fqcn).toEqual('NotEvaluated');
+
+        const [count, value, iterator, registered] = data.members;
+        expect(count).toMatchObject({
+            kind: 'field',
+            name: '#count',
+            static: true,
+            private: true,
+            computed: false,
+        });
+        expect(value).toMatchObject({ kind: 'getter', name: 'value' });
+        expect(iterator.name).toBe(Symbol.iterator);
+        expect(iterator.computed).toBe(true);
+        expect(registered.name).toBe(Symbol.for('registered'));
+        expect(registered.parameters[1].default).toEqual(2);
+    });
+
+    it('should expose typescript type annotations', () => {
+        const { getReflectionData } = require('../../lib/reflection');
+
+        const { classIds } = compileDetailed(
+            `
+import type { Logger } from './logger';
+
+export default class Typed {
+    private readonly name: string | null = null;
+
+    constructor(protected logger: Logger) {}
+
+    greet(greeting: string = 'hello'): Promise<void> {}
+}
+`,
+            'typed.ts',
+        );
+
+        const { members } = getReflectionData(classIds[0]);
+        const [name, constructor, greet] = members;
+        expect(name.type.text).toEqual('string | null');
+        expect(name.accessibility).toEqual('private');
+        expect(name.readonly).toBe(true);
+
+        const [logger] = constructor.parameters;
+        expect(logger.type).toEqual({
+            text: 'Logger',
+            references: [
+                { name: 'Logger', specifier: './logger', imported: 'Logger' },
+            ],
+        });
+        expect(logger.accessibility).toEqual('protected');
+
+        expect(greet.parameters[0].type.text).toEqual('string');
+        expect(greet.returnType.text).toEqual('Promise<void>');
+        expect(greet.accessibility).toBeNull();
+    });
+
+    it('should emit typescript design metadata', () => {
+        const program = `
+class Logger {}
+const injectable = () => () => {};
+const inject = () => () => {};
+
+@injectable()
+export default class Service {
+    constructor(private logger: Logger, name: string, missing: Missing) {}
+
+    @inject()
+    handler: Logger;
+
+    @inject()
+    handle(count: number, self: Service): boolean {
+        return true;
+    }
+}
+`;
+
+        const compiled = compile(program, 'design.ts', {
+            emitDecoratorMetadata: true,
+        });
+
+        const exports = {};
+        runInThisContext('(function(exports) {\n' + compiled + '\n})')(exports);
+
+        const Service = exports['default'];
+        const names = (types) => types.map((t) => t.name);
+        expect(
+            names(getDesignMetadata(Service, 'design:paramtypes')),
+        ).toEqual(['Logger', 'String', 'Object']);
+        expect(
+            getDesignMetadata(Service, 'design:type', 'handler').name,
+        ).toEqual('Logger');
+        expect(
+            names(getDesignMetadata(Service, 'design:paramtypes', 'handle')),
+        ).toEqual(['Number', 'Service']);
+        expect(getDesignMetadata(Service, 'design:returntype', 'handle')).toBe(
+            Boolean,
+        );
+        expect(getDesignMetadata(Service, 'design:type', 'missing')).toBe(
+            undefined,
+        );
+    });
 });